@@ -18,8 +18,8 @@ pub mod signatures;
 
 pub use connection::SolanaConnection;
 pub use resolve::{
-    InstructionGroup, ResolverResult, SerializableAccountMeta, SerializableInstruction,
-    RESOLVER_PUBKEY_SHIM_VAA_SIGS,
+    build_resolver_instruction_data, InstructionGroup, ResolveOptions, ResolverResult,
+    SerializableAccountMeta, SerializableInstruction, RESOLVER_PUBKEY_SHIM_VAA_SIGS,
 };
 pub use signatures::{build_close_signatures_ix, build_post_signatures_ix, PostedSignatures};
 
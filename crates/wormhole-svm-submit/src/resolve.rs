@@ -5,8 +5,8 @@
 
 use borsh::BorshDeserialize;
 use executor_account_resolver_svm::{
-    InstructionGroups, MissingAccounts, Resolver, RESOLVER_EXECUTE_VAA_V1,
-    RESOLVER_PUBKEY_GUARDIAN_SET, RESOLVER_PUBKEY_PAYER,
+    InstructionGroups, MissingAccounts, Resolver, RESOLVER_PUBKEY_GUARDIAN_SET,
+    RESOLVER_PUBKEY_PAYER,
 };
 use solana_sdk::{
     instruction::{AccountMeta, Instruction},
@@ -19,16 +19,66 @@ use crate::connection::SolanaConnection;
 use crate::SubmitError;
 
 pub use executor_account_resolver_svm::{
-    InstructionGroup, SerializableAccountMeta, SerializableInstruction,
+    InstructionGroup, SerializableAccountMeta, SerializableInstruction, RESOLVER_EXECUTE_VAA_V1,
     RESOLVER_PUBKEY_SHIM_VAA_SIGS,
 };
 
 /// Result of running the resolver.
+///
+/// Marked `#[non_exhaustive]` so diagnostics can be added without breaking
+/// callers; build one outside this crate with [`ResolverResult::new`].
+#[non_exhaustive]
 pub struct ResolverResult {
     /// The resolved instruction groups.
     pub instruction_groups: Vec<InstructionGroup>,
     /// How many iterations it took to resolve.
     pub iterations: usize,
+    /// The accounts the program reported missing on each non-final iteration,
+    /// after placeholder substitution, in the order they were requested.
+    pub missing_per_iteration: Vec<Vec<Pubkey>>,
+    /// The raw return data of each iteration, including the final one. Empty
+    /// unless [`ResolveOptions::capture_return_data`] is set.
+    pub raw_return_data: Vec<Vec<u8>>,
+}
+
+impl ResolverResult {
+    /// A result resolved in `iterations` rounds, with the accounts requested
+    /// on each non-final round and no captured return data.
+    ///
+    /// For building expected results in tests; the resolver loop fills in
+    /// every field itself.
+    pub fn new(
+        instruction_groups: Vec<InstructionGroup>,
+        iterations: usize,
+        missing_per_iteration: Vec<Vec<Pubkey>>,
+    ) -> Self {
+        Self {
+            instruction_groups,
+            iterations,
+            missing_per_iteration,
+            raw_return_data: Vec::new(),
+        }
+    }
+}
+
+/// Diagnostic options for [`resolve_execute_vaa_v1_with_options`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ResolveOptions {
+    /// Keep each iteration's raw return data in [`ResolverResult::raw_return_data`],
+    /// and include the bytes in the error when they fail to deserialize.
+    pub capture_return_data: bool,
+}
+
+/// Build the instruction data for `resolve_execute_vaa_v1`.
+///
+/// Layout: the 8-byte [`RESOLVER_EXECUTE_VAA_V1`] discriminator followed by the
+/// VAA body as a borsh `Vec<u8>` (4-byte little-endian length, then the bytes).
+pub fn build_resolver_instruction_data(vaa_body: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + 4 + vaa_body.len());
+    data.extend_from_slice(&RESOLVER_EXECUTE_VAA_V1);
+    data.extend_from_slice(&(vaa_body.len() as u32).to_le_bytes());
+    data.extend_from_slice(vaa_body);
+    data
 }
 
 /// Run the executor-account-resolver `resolve_execute_vaa_v1` loop.
@@ -48,21 +98,37 @@ pub fn resolve_execute_vaa_v1<C: SolanaConnection>(
     vaa_body: &[u8],
     guardian_set: &Pubkey,
     max_iterations: usize,
+) -> Result<ResolverResult, SubmitError> {
+    resolve_execute_vaa_v1_with_options(
+        conn,
+        program_id,
+        payer,
+        vaa_body,
+        guardian_set,
+        max_iterations,
+        ResolveOptions::default(),
+    )
+}
+
+/// Like [`resolve_execute_vaa_v1`], with diagnostic [`ResolveOptions`].
+pub fn resolve_execute_vaa_v1_with_options<C: SolanaConnection>(
+    conn: &C,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    vaa_body: &[u8],
+    guardian_set: &Pubkey,
+    max_iterations: usize,
+    options: ResolveOptions,
 ) -> Result<ResolverResult, SubmitError> {
     let mut remaining_accounts: Vec<AccountMeta> = Vec::new();
+    let mut missing_per_iteration: Vec<Vec<Pubkey>> = Vec::new();
+    let mut raw_return_data: Vec<Vec<u8>> = Vec::new();
 
     for iteration in 1..=max_iterations {
-        // Build the resolver instruction data:
-        // 8-byte discriminator + borsh Vec<u8> (4-byte LE length + bytes)
-        let mut ix_data = Vec::with_capacity(8 + 4 + vaa_body.len());
-        ix_data.extend_from_slice(&RESOLVER_EXECUTE_VAA_V1);
-        ix_data.extend_from_slice(&(vaa_body.len() as u32).to_le_bytes());
-        ix_data.extend_from_slice(vaa_body);
-
         let ix = Instruction {
             program_id: *program_id,
             accounts: remaining_accounts.clone(),
-            data: ix_data,
+            data: build_resolver_instruction_data(vaa_body),
         };
 
         let blockhash = conn
@@ -88,27 +154,47 @@ pub fn resolve_execute_vaa_v1<C: SolanaConnection>(
 
         let resolver: Resolver<InstructionGroups> =
             BorshDeserialize::deserialize(&mut return_data.as_slice()).map_err(|e| {
-                SubmitError::ResolverSimulation(format!(
-                    "Failed to deserialize resolver return data: {}",
-                    e
-                ))
+                if options.capture_return_data {
+                    SubmitError::ResolverSimulation(format!(
+                        "Failed to deserialize resolver return data on iteration {}: {} \
+                         (raw return data: {:?})",
+                        iteration, e, return_data
+                    ))
+                } else {
+                    SubmitError::ResolverSimulation(format!(
+                        "Failed to deserialize resolver return data: {}",
+                        e
+                    ))
+                }
             })?;
 
+        if options.capture_return_data {
+            raw_return_data.push(return_data);
+        }
+
         match resolver {
             Resolver::Resolved(groups) => {
                 return Ok(ResolverResult {
                     instruction_groups: groups.0,
                     iterations: iteration,
+                    missing_per_iteration,
+                    raw_return_data,
                 });
             }
             Resolver::Missing(MissingAccounts {
                 accounts: missing,
                 address_lookup_tables: _,
             }) => {
-                for pubkey in &missing {
-                    let actual = substitute_placeholder(*pubkey, &payer.pubkey(), guardian_set);
-                    remaining_accounts.push(AccountMeta::new_readonly(actual, false));
-                }
+                let requested: Vec<Pubkey> = missing
+                    .iter()
+                    .map(|pubkey| substitute_placeholder(*pubkey, &payer.pubkey(), guardian_set))
+                    .collect();
+                remaining_accounts.extend(
+                    requested
+                        .iter()
+                        .map(|pubkey| AccountMeta::new_readonly(*pubkey, false)),
+                );
+                missing_per_iteration.push(requested);
             }
             Resolver::Account() => {
                 return Err(SubmitError::ResolverSimulation(
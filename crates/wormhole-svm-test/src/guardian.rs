@@ -9,11 +9,141 @@ pub const DEFAULT_GUARDIAN_SECRET_KEY: [u8; 32] = [
     0x6b, 0xc6, 0x83, 0x53, 0x64, 0x55, 0x71, 0xa8, 0xcf, 0xf5, 0x16, 0xab, 0x2e, 0xe1, 0x13, 0xa0,
 ];
 
+/// Ethereum address of [`DEFAULT_GUARDIAN_SECRET_KEY`]:
+/// `0xbeFA429d57cD18b7F8A4d91A2da9AB4AF05d0FBe`, guardian 0 of the Wormhole
+/// devnet (Tilt) guardian set.
+///
+/// VAAs signed by [`TestGuardian::default`] verify against any reference
+/// implementation configured with the devnet guardian set.
+pub const DEVNET_GUARDIAN_ADDRESS: [u8; 20] = [
+    0xbe, 0xfa, 0x42, 0x9d, 0x57, 0xcd, 0x18, 0xb7, 0xf8, 0xa4, 0xd9, 0x1a, 0x2d, 0xa9, 0xab, 0x4a,
+    0xf0, 0x5d, 0x0f, 0xbe,
+];
+
+/// Placeholder address written into the slot of a guardian index that no
+/// guardian in a [`TestGuardianSet`] holds (see
+/// [`TestGuardianSet::from_indexed`]).
+///
+/// No signature recovers to the zero address, so a signature claiming a gap
+/// index fails recovery, on chain and offline alike.
+pub const GAP_GUARDIAN_ADDRESS: [u8; 20] = [0; 20];
+
+/// The secp256k1 curve order `n`, big-endian.
+const SECP256K1_ORDER: [u8; 32] = [
+    0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe,
+    0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36, 0x41, 0x41,
+];
+
+/// Derive a guardian's Ethereum address from a raw 64-byte public key.
+///
+/// The input is the uncompressed secp256k1 public key without the leading
+/// `0x04` prefix (i.e. `x || y`). The address is the last 20 bytes of
+/// keccak256 over those 64 bytes.
+pub fn eth_address_from_pubkey(pubkey: &[u8; 64]) -> [u8; 20] {
+    let hash = Keccak256::digest(pubkey);
+    hash[12..32].try_into().unwrap()
+}
+
+/// Minimum number of signatures required for quorum in a set of `num_guardians`.
+///
+/// Wormhole requires more than two thirds of the guardians: `floor(2n/3) + 1`.
+pub fn quorum(num_guardians: usize) -> usize {
+    num_guardians * 2 / 3 + 1
+}
+
+/// Assemble a 66-byte guardian signature: `[guardian_index][r (32)][s (32)][v (1)]`.
+///
+/// The index comes first, followed by the 65-byte recoverable signature from
+/// [`TestGuardian::sign`]. This is the layout both inside a VAA and in the
+/// posted-signatures account.
+pub fn guardian_signature_bytes(index: u8, signature: &[u8; 65]) -> [u8; 66] {
+    let mut bytes = [0u8; 66];
+    bytes[0] = index;
+    bytes[1..].copy_from_slice(signature);
+    bytes
+}
+
+/// Number of distinct guardian indices among 66-byte guardian signatures.
+///
+/// Duplicate signatures from the same guardian only count once.
+pub fn distinct_guardian_count(signatures: &[[u8; 66]]) -> usize {
+    let mut seen = [false; 256];
+    signatures
+        .iter()
+        .filter(|sig| !std::mem::replace(&mut seen[sig[0] as usize], true))
+        .count()
+}
+
+/// Whether two batches of 66-byte guardian signatures hold the same signatures,
+/// regardless of order.
+///
+/// Both batches are sorted by guardian index (then by signature bytes) before
+/// comparing, so duplicates must appear equally often in each. Use it to
+/// check a helper that reorders signatures against an unsorted expectation.
+pub fn signatures_equal_unordered(a: &[[u8; 66]], b: &[[u8; 66]]) -> bool {
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.sort_unstable();
+    b.sort_unstable();
+    a == b
+}
+
+/// Assert that the guardian indices of a signature batch are strictly
+/// increasing, as the verify shim requires.
+///
+/// Catches ordering and duplicate bugs before the transaction is sent. Panics
+/// with the position of the first non-increasing pair.
+pub fn assert_signatures_sorted(signatures: &[[u8; 66]]) {
+    if let Some(pos) = signatures.windows(2).position(|w| w[0][0] >= w[1][0]) {
+        panic!(
+            "guardian indices not strictly increasing at position {}: {} then {}",
+            pos + 1,
+            signatures[pos][0],
+            signatures[pos + 1][0]
+        );
+    }
+}
+
+/// Domain prefix guardians sign Cross-Chain Query responses under.
+pub const QUERY_RESPONSE_PREFIX: &[u8] = b"query_response_0000000000000000000|";
+
+/// A message domain guardians sign in.
+///
+/// Every scheme signs `keccak256(prefix || keccak256(message))`. VAAs use an
+/// empty prefix, which reduces to the double keccak256 of the VAA body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigningScheme {
+    /// VAA bodies (empty prefix).
+    Vaa,
+    /// Cross-Chain Query responses ([`QUERY_RESPONSE_PREFIX`]).
+    Query,
+}
+
+impl SigningScheme {
+    /// The domain prefix prepended to the message hash before the final hash.
+    pub fn prefix(&self) -> &'static [u8] {
+        match self {
+            SigningScheme::Vaa => b"",
+            SigningScheme::Query => QUERY_RESPONSE_PREFIX,
+        }
+    }
+
+    /// Compute the 32-byte digest guardians sign for `message` in this scheme.
+    pub fn digest(&self, message: &[u8]) -> [u8; 32] {
+        let message_hash = Keccak256::digest(message);
+
+        let mut hasher = Keccak256::new();
+        hasher.update(self.prefix());
+        hasher.update(message_hash);
+        hasher.finalize().into()
+    }
+}
+
 /// A test guardian with signing capabilities.
 #[derive(Clone)]
 pub struct TestGuardian {
     secret_key: SecretKey,
-    /// The guardian's public key.
+    /// The guardian's public key (see the [`secp256k1`](crate::secp256k1) re-export).
     pub public_key: PublicKey,
     /// The guardian's Ethereum address (last 20 bytes of keccak256(pubkey)).
     pub eth_address: [u8; 20],
@@ -27,10 +157,10 @@ impl TestGuardian {
         let secret_key = SecretKey::parse(&secret_key).expect("Invalid secret key");
         let public_key = PublicKey::from_secret_key(&secret_key);
 
-        // Derive Ethereum address from public key (last 20 bytes of keccak256(pubkey[1..]))
+        // Derive Ethereum address from the uncompressed public key
         let pubkey_bytes = public_key.serialize();
-        let hash = Keccak256::digest(&pubkey_bytes[1..]); // Skip the 0x04 prefix
-        let eth_address: [u8; 20] = hash[12..32].try_into().unwrap();
+        let raw: [u8; 64] = pubkey_bytes[1..].try_into().unwrap(); // Skip the 0x04 prefix
+        let eth_address = eth_address_from_pubkey(&raw);
 
         Self {
             secret_key,
@@ -40,6 +170,39 @@ impl TestGuardian {
         }
     }
 
+    /// Override the guardian index embedded in this guardian's signatures.
+    ///
+    /// The index also picks the guardian's slot in
+    /// [`TestGuardianSet::eth_addresses`] (and therefore in the installed
+    /// account), so an overridden guardian used outside its set signs for
+    /// another guardian's slot. That tests that verification looks up the key
+    /// by the embedded index:
+    ///
+    /// ```rust
+    /// use wormhole_svm_test::{verify_signatures, TestGuardianSet, TestVaa, VerifyError};
+    ///
+    /// let honest = TestGuardianSet::generate(2, 1);
+    /// // Guardian 0 claims to be guardian 1.
+    /// let impostor = honest.get(0).unwrap().clone().with_index(1);
+    ///
+    /// let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+    /// let sigs = [
+    ///     honest.get(0).unwrap().sign_vaa_body(&vaa.body()),
+    ///     impostor.sign_vaa_body(&vaa.body()),
+    /// ];
+    /// assert_eq!(sigs[1][0], 1);
+    ///
+    /// // The signature recovers to guardian 0's key, not guardian 1's.
+    /// assert!(matches!(
+    ///     verify_signatures(&vaa.digest(), &sigs, &honest.eth_addresses()),
+    ///     Err(VerifyError::KeyMismatch { position: 1, index: 1, .. })
+    /// ));
+    /// ```
+    pub fn with_index(mut self, index: u8) -> Self {
+        self.index = index;
+        self
+    }
+
     /// Create a test guardian from a hex-encoded secret key.
     pub fn from_hex(hex_key: &str, index: u8) -> Result<Self, hex::FromHexError> {
         let bytes: [u8; 32] = hex::decode(hex_key)?
@@ -59,21 +222,60 @@ impl TestGuardian {
         sig_bytes
     }
 
+    /// Sign a 32-byte digest and return the compact signature `r || s` and the
+    /// recovery id separately.
+    ///
+    /// Same signature as [`sign`](Self::sign), split the way most other
+    /// secp256k1 libraries (e.g. ethers, go-ethereum) expose it.
+    pub fn sign_compact(&self, digest: &[u8; 32]) -> ([u8; 64], u8) {
+        let signature = self.sign(digest);
+        (signature[..64].try_into().unwrap(), signature[64])
+    }
+
+    /// Sign a 32-byte digest and return the malleated, high-S form of the
+    /// signature.
+    ///
+    /// [`sign`](Self::sign) always returns the canonical low-S signature. This
+    /// replaces `s` with `n - s` (where `n` is the curve order) and flips the
+    /// recovery id's parity, which still recovers to this guardian but is
+    /// non-canonical, for checking that verifiers reject malleated signatures.
+    pub fn sign_high_s(&self, digest: &[u8; 32]) -> [u8; 65] {
+        let mut signature = self.sign(digest);
+
+        let mut borrow = 0u16;
+        for i in (0..32).rev() {
+            let diff = SECP256K1_ORDER[i] as u16 + 0x100 - signature[32 + i] as u16 - borrow;
+            signature[32 + i] = diff as u8;
+            borrow = if diff < 0x100 { 1 } else { 0 };
+        }
+        signature[64] ^= 1;
+
+        signature
+    }
+
+    /// Sign a VAA body with [`sign_high_s`](Self::sign_high_s) and return a
+    /// 66-byte guardian signature.
+    pub fn sign_vaa_body_high_s(&self, vaa_body: &[u8]) -> [u8; 66] {
+        guardian_signature_bytes(
+            self.index,
+            &self.sign_high_s(&SigningScheme::Vaa.digest(vaa_body)),
+        )
+    }
+
     /// Sign a VAA body and return a 66-byte guardian signature.
     ///
     /// Format: [guardian_index (1 byte), signature (65 bytes)]
     ///
     /// The VAA body is double-hashed with keccak256 per Wormhole protocol.
     pub fn sign_vaa_body(&self, vaa_body: &[u8]) -> [u8; 66] {
-        let message_hash = Keccak256::digest(vaa_body);
-        let digest: [u8; 32] = Keccak256::digest(message_hash).into();
-
-        let signature = self.sign(&digest);
+        self.sign_scheme(SigningScheme::Vaa, vaa_body)
+    }
 
-        let mut result = [0u8; 66];
-        result[0] = self.index;
-        result[1..66].copy_from_slice(&signature);
-        result
+    /// Sign `message` under `scheme` and return a 66-byte guardian signature.
+    ///
+    /// Format: [guardian_index (1 byte), signature (65 bytes)]
+    pub fn sign_scheme(&self, scheme: SigningScheme, message: &[u8]) -> [u8; 66] {
+        guardian_signature_bytes(self.index, &self.sign(&scheme.digest(message)))
     }
 }
 
@@ -87,12 +289,31 @@ impl Default for TestGuardian {
 #[derive(Clone)]
 pub struct TestGuardianSet {
     guardians: Vec<TestGuardian>,
+    index: Option<u32>,
 }
 
 impl TestGuardianSet {
     /// Create a guardian set from a list of guardians.
     pub fn new(guardians: Vec<TestGuardian>) -> Self {
-        Self { guardians }
+        Self {
+            guardians,
+            index: None,
+        }
+    }
+
+    /// Associate this set with an on-chain guardian set index.
+    ///
+    /// Helpers that install the set (e.g. `setup_wormhole`) check that the
+    /// index they are given matches, so a set can't accidentally be installed
+    /// under the wrong index.
+    pub fn with_index(mut self, index: u32) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// The guardian set index this set is associated with, if any.
+    pub fn index(&self) -> Option<u32> {
+        self.index
     }
 
     /// Create a guardian set with a single guardian.
@@ -113,7 +334,51 @@ impl TestGuardianSet {
                 TestGuardian::new(secret, i as u8)
             })
             .collect();
-        Self { guardians }
+        Self::new(guardians)
+    }
+
+    /// Create a guardian set from explicit `(index, guardian)` pairs, allowing gaps.
+    ///
+    /// Real guardian sets are always contiguous (`0..n`); this is for hardening
+    /// tests that check code doesn't assume so. Each guardian signs with its
+    /// given index, and the set is ordered by ascending index.
+    /// [`eth_addresses`](Self::eth_addresses) fills each gap with
+    /// [`GAP_GUARDIAN_ADDRESS`] so that a guardian's slot is always its index:
+    /// for pairs `(0, a), (1, b), (3, c)` it is `[a, b, GAP, c]`, and the
+    /// installed set has four keys.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index appears more than once.
+    pub fn from_indexed(pairs: Vec<(u8, TestGuardian)>) -> Self {
+        let mut guardians: Vec<TestGuardian> = pairs
+            .into_iter()
+            .map(|(index, guardian)| guardian.with_index(index))
+            .collect();
+        guardians.sort_by_key(|g| g.index);
+
+        if let Some(pair) = guardians.windows(2).find(|w| w[0].index == w[1].index) {
+            panic!("duplicate guardian index {}", pair[0].index);
+        }
+
+        Self::new(guardians)
+    }
+
+    /// Generate N guardians with random secret keys from the OS RNG.
+    ///
+    /// Unlike [`generate`](Self::generate), the keys (and therefore the
+    /// addresses) differ on every run and cannot be reproduced. Use this to
+    /// check that code under test makes no assumptions about specific guardian
+    /// addresses; use `generate` when a failure needs to be replayable.
+    #[cfg(feature = "random")]
+    pub fn random(count: usize) -> Self {
+        let guardians = (0..count)
+            .map(|i| {
+                let secret = SecretKey::random(&mut rand::rngs::OsRng);
+                TestGuardian::new(secret.serialize(), i as u8)
+            })
+            .collect();
+        Self::new(guardians)
     }
 
     /// Sign a VAA body with all guardians in the set.
@@ -124,18 +389,133 @@ impl TestGuardianSet {
             .collect()
     }
 
+    /// Sign many VAA bodies with all guardians in the set.
+    ///
+    /// Returns one batch of signatures per body, in order; each batch equals
+    /// [`sign_vaa_body`](Self::sign_vaa_body) on that body. Each body is hashed
+    /// once rather than once per guardian, so this is the path to benchmark
+    /// signing throughput.
+    pub fn sign_many(&self, vaa_bodies: &[&[u8]]) -> Vec<Vec<[u8; 66]>> {
+        vaa_bodies
+            .iter()
+            .map(|body| {
+                let digest = SigningScheme::Vaa.digest(body);
+                self.guardians
+                    .iter()
+                    .map(|g| guardian_signature_bytes(g.index, &g.sign(&digest)))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Sign a VAA body with every guardian, corrupting the signatures of the
+    /// guardians at `corrupt_indices`.
+    ///
+    /// Corrupted signatures have the last byte of `r` flipped but keep their
+    /// guardian index byte, so a verifier gets past the index checks and fails
+    /// at signature recovery (the signature recovers to some other address, or
+    /// to none). Unknown indices are ignored.
+    pub fn sign_vaa_body_corrupted(
+        &self,
+        vaa_body: &[u8],
+        corrupt_indices: &[u8],
+    ) -> Vec<[u8; 66]> {
+        let mut signatures = self.sign_vaa_body(vaa_body);
+        for sig in &mut signatures {
+            if corrupt_indices.contains(&sig[0]) {
+                sig[32] ^= 0xFF;
+            }
+        }
+        signatures
+    }
+
+    /// Minimum number of signatures for quorum on this set: [`quorum`] of its
+    /// slot count (see [`eth_addresses`](Self::eth_addresses)), which is its
+    /// size unless it has gaps.
+    pub fn quorum(&self) -> usize {
+        quorum(self.slots())
+    }
+
+    /// Sign a VAA body with the first [`quorum`](Self::quorum) guardians in
+    /// index order, the smallest batch that verifies.
+    pub fn sign_vaa_body_quorum(&self, vaa_body: &[u8]) -> Vec<[u8; 66]> {
+        self.guardians
+            .iter()
+            .take(self.quorum())
+            .map(|g| g.sign_vaa_body(vaa_body))
+            .collect()
+    }
+
     /// Sign a VAA body with specific guardians (by index).
+    ///
+    /// Guardians are selected by their [`index`](TestGuardian::index), which
+    /// equals their position except in sets built with
+    /// [`from_indexed`](Self::from_indexed). Signatures follow the order of
+    /// `indices`, and unknown indices are skipped; use
+    /// [`sign_vaa_body_unsorted`](Self::sign_vaa_body_unsorted) when a
+    /// skipped index would hide a fixture mistake.
     pub fn sign_vaa_body_with(&self, vaa_body: &[u8], indices: &[u8]) -> Vec<[u8; 66]> {
         indices
             .iter()
-            .filter_map(|&i| self.guardians.get(i as usize))
+            .filter_map(|&i| self.guardians.iter().find(|g| g.index == i))
             .map(|g| g.sign_vaa_body(vaa_body))
             .collect()
     }
 
-    /// Get the Ethereum addresses of all guardians.
+    /// Sign a VAA body with the guardians at `indices`, in exactly that order.
+    ///
+    /// Descending and duplicated indices are kept as given, so the result is
+    /// intentionally protocol-invalid whenever `indices` is not strictly
+    /// increasing: use it to confirm that `post_signatures` or the verify CPI
+    /// rejects out-of-order batches. Unlike
+    /// [`sign_vaa_body_with`](Self::sign_vaa_body_with), every index must
+    /// belong to a guardian, so the result always has one signature per entry
+    /// of `indices`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index does not belong to any guardian in the set.
+    pub fn sign_vaa_body_unsorted(&self, vaa_body: &[u8], indices: &[u8]) -> Vec<[u8; 66]> {
+        let digest = SigningScheme::Vaa.digest(vaa_body);
+        indices
+            .iter()
+            .map(|&i| {
+                let guardian = self
+                    .guardians
+                    .iter()
+                    .find(|g| g.index == i)
+                    .unwrap_or_else(|| panic!("no guardian with index {} in the set", i));
+                guardian_signature_bytes(i, &guardian.sign(&digest))
+            })
+            .collect()
+    }
+
+    /// Get the Ethereum addresses of all guardians, one slot per guardian index.
+    ///
+    /// The address at position `i` is that of the guardian signing with index
+    /// `i`, or [`GAP_GUARDIAN_ADDRESS`] if no guardian has that index. This is
+    /// the key list the set is installed with, and every check in this crate
+    /// treats a signature's index as a slot in it: an index is in range if it
+    /// is below the slot count.
     pub fn eth_addresses(&self) -> Vec<[u8; 20]> {
-        self.guardians.iter().map(|g| g.eth_address).collect()
+        (0..self.slots())
+            .map(|slot| {
+                self.guardians
+                    .iter()
+                    .find(|g| g.index as usize == slot)
+                    .map_or(GAP_GUARDIAN_ADDRESS, |g| g.eth_address)
+            })
+            .collect()
+    }
+
+    /// Number of slots in [`eth_addresses`](Self::eth_addresses): one past the
+    /// highest guardian index.
+    fn slots(&self) -> usize {
+        self.guardians
+            .iter()
+            .map(|g| g.index as usize + 1)
+            .max()
+            .unwrap_or(0)
     }
 
     /// Get the number of guardians in the set.
@@ -157,6 +537,171 @@ impl TestGuardianSet {
     pub fn iter(&self) -> impl Iterator<Item = &TestGuardian> {
         self.guardians.iter()
     }
+
+    /// Check whether `signatures` reach quorum for this set.
+    ///
+    /// Counts distinct guardian indices that are in range for the set (below
+    /// its slot count, see [`eth_addresses`](Self::eth_addresses)) and
+    /// compares against [`quorum`](Self::quorum). This only inspects indices,
+    /// so a signature claiming a gap index counts; use
+    /// [`verify_signatures`](crate::verify_signatures) to also check ordering
+    /// and that each signature recovers to the right guardian.
+    pub fn has_quorum(&self, signatures: &[[u8; 66]]) -> bool {
+        let slots = self.slots();
+        let in_set: Vec<[u8; 66]> = signatures
+            .iter()
+            .filter(|sig| (sig[0] as usize) < slots)
+            .copied()
+            .collect();
+        distinct_guardian_count(&in_set) >= self.quorum()
+    }
+
+    /// Serialize this set as Core Bridge guardian set account data at `index`.
+    ///
+    /// Addresses are written in *index order*: the address at position `i` is
+    /// the guardian that signs with index `i`. This is how real sets are
+    /// stored, and index order is generally *not* sorted by address. Creation
+    /// and expiration times are 0 (never expires).
+    pub fn to_account_data(&self, index: u32) -> Vec<u8> {
+        guardian_set_account_data(&self.eth_addresses(), index, 0)
+    }
+
+    /// Parse Core Bridge guardian set account data, e.g. a dump of a real
+    /// account, into an [`AddressOnlyGuardianSet`].
+    ///
+    /// The inverse of [`to_account_data`](Self::to_account_data). Only the
+    /// addresses are on chain, so the result cannot sign; use it to reproduce
+    /// production state for layout and verification tests.
+    pub fn from_account_data(data: &[u8]) -> Result<AddressOnlyGuardianSet, crate::VerifyError> {
+        let (index, addresses) = crate::verify::parse_guardian_set_account(data)?;
+
+        let times = 8 + 20 * addresses.len();
+        let Some(times) = data.get(times..times + 8) else {
+            return Err(crate::VerifyError::MalformedGuardianSet(format!(
+                "{} bytes cannot hold the creation and expiration times",
+                data.len()
+            )));
+        };
+
+        Ok(AddressOnlyGuardianSet {
+            index,
+            addresses,
+            creation_time: u32::from_le_bytes(times[0..4].try_into().unwrap()),
+            expiration_time: u32::from_le_bytes(times[4..8].try_into().unwrap()),
+        })
+    }
+
+    /// Serialize this set with its addresses in descending address order.
+    ///
+    /// A correctness probe, not a real layout: on-chain sets are never sorted
+    /// by address (see [`to_account_data`](Self::to_account_data)). Writing
+    /// the addresses deliberately out of ascending order, and out of index
+    /// order, exposes code that binary-searches guardian addresses or
+    /// otherwise assumes they are sorted. Signatures no longer line up with
+    /// the slot for their guardian index, so verification against this data is
+    /// expected to fail wherever the order differs from index order.
+    pub fn to_account_data_unsorted(&self, index: u32) -> Vec<u8> {
+        let mut addresses = self.eth_addresses();
+        addresses.sort_unstable_by(|a, b| b.cmp(a));
+        guardian_set_account_data(&addresses, index, 0)
+    }
+
+    /// Compare this set against `other` (e.g. the next set in a rotation) by
+    /// Ethereum address.
+    pub fn diff(&self, other: &TestGuardianSet) -> GuardianSetDiff {
+        let before: Vec<[u8; 20]> = self.guardians.iter().map(|g| g.eth_address).collect();
+        let after: Vec<[u8; 20]> = other.guardians.iter().map(|g| g.eth_address).collect();
+
+        GuardianSetDiff {
+            added: after
+                .iter()
+                .filter(|a| !before.contains(a))
+                .copied()
+                .collect(),
+            removed: before
+                .iter()
+                .filter(|a| !after.contains(a))
+                .copied()
+                .collect(),
+            retained: before
+                .iter()
+                .filter(|a| after.contains(a))
+                .copied()
+                .collect(),
+        }
+    }
+}
+
+/// Build Core Bridge guardian set account data:
+/// `index u32 | keys_len u32 | keys (20 each) | creation_time u32 | expiration_time u32`,
+/// all little-endian.
+pub(crate) fn guardian_set_account_data(
+    addresses: &[[u8; 20]],
+    index: u32,
+    expiration_time: u32,
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(16 + 20 * addresses.len());
+
+    // Guardian set index
+    data.extend_from_slice(&index.to_le_bytes());
+
+    // Number of keys
+    data.extend_from_slice(&(addresses.len() as u32).to_le_bytes());
+
+    // Guardian Ethereum addresses
+    for addr in addresses {
+        data.extend_from_slice(addr);
+    }
+
+    // Creation time (0 for testing)
+    data.extend_from_slice(&0u32.to_le_bytes());
+
+    // Expiration time (0 = never expires)
+    data.extend_from_slice(&expiration_time.to_le_bytes());
+
+    data
+}
+
+/// A guardian set known only by its on-chain account data, from
+/// [`TestGuardianSet::from_account_data`].
+///
+/// Holds no secret keys, so it can check signatures (with
+/// [`verify_signatures`](crate::verify_signatures) over `addresses`) but not
+/// produce them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressOnlyGuardianSet {
+    /// Guardian set index.
+    pub index: u32,
+    /// Guardian Ethereum addresses, in index order.
+    pub addresses: Vec<[u8; 20]>,
+    /// Unix time the set was created.
+    pub creation_time: u32,
+    /// Unix time the set expires (0 = never).
+    pub expiration_time: u32,
+}
+
+impl AddressOnlyGuardianSet {
+    /// Serialize back to Core Bridge guardian set account data, byte-for-byte
+    /// equal to the data it was parsed from.
+    pub fn to_account_data(&self) -> Vec<u8> {
+        let mut data = guardian_set_account_data(&self.addresses, self.index, self.expiration_time);
+        let creation = 8 + 20 * self.addresses.len();
+        data[creation..creation + 4].copy_from_slice(&self.creation_time.to_le_bytes());
+        data
+    }
+}
+
+/// Membership changes between two guardian sets, from [`TestGuardianSet::diff`].
+///
+/// Addresses keep the order of the set they were taken from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct GuardianSetDiff {
+    /// Addresses in the new set but not the old one.
+    pub added: Vec<[u8; 20]>,
+    /// Addresses in the old set but not the new one.
+    pub removed: Vec<[u8; 20]>,
+    /// Addresses in both sets (in old-set order).
+    pub retained: Vec<[u8; 20]>,
 }
 
 impl Default for TestGuardianSet {
@@ -179,6 +724,19 @@ mod tests {
         assert_eq!(guardian.eth_address, guardian2.eth_address);
     }
 
+    #[test]
+    fn test_default_guardian_is_devnet_guardian() {
+        assert_eq!(TestGuardian::default().eth_address, DEVNET_GUARDIAN_ADDRESS);
+    }
+
+    #[test]
+    fn test_eth_address_from_pubkey_matches_guardian() {
+        let guardian = TestGuardian::default();
+        let raw: [u8; 64] = guardian.public_key.serialize()[1..].try_into().unwrap();
+
+        assert_eq!(eth_address_from_pubkey(&raw), guardian.eth_address);
+    }
+
     #[test]
     fn test_sign_vaa_body() {
         let guardian = TestGuardian::default();
@@ -190,6 +748,58 @@ mod tests {
         assert_eq!(sig[0], 0); // guardian index
     }
 
+    #[test]
+    fn test_sign_many_matches_individual_signing() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let bodies: [&[u8]; 2] = [b"first", b"second"];
+
+        let batches = guardians.sign_many(&bodies);
+
+        assert_eq!(batches.len(), 2);
+        for (body, batch) in bodies.iter().zip(&batches) {
+            assert_eq!(batch, &guardians.sign_vaa_body(body));
+        }
+    }
+
+    #[test]
+    fn test_sign_compact_matches_sign() {
+        let guardian = TestGuardian::default();
+        let digest = [0x42; 32];
+
+        let (compact, recovery_id) = guardian.sign_compact(&digest);
+        let full = guardian.sign(&digest);
+
+        assert_eq!(&full[..64], &compact);
+        assert_eq!(full[64], recovery_id);
+        assert!(recovery_id <= 1);
+    }
+
+    #[test]
+    fn test_sign_scheme() {
+        let guardian = TestGuardian::default();
+        let message = b"query response bytes";
+
+        // The VAA scheme is the classic double keccak256.
+        let vaa_digest: [u8; 32] = Keccak256::digest(Keccak256::digest(message)).into();
+        assert_eq!(SigningScheme::Vaa.digest(message), vaa_digest);
+        assert_eq!(
+            guardian.sign_scheme(SigningScheme::Vaa, message),
+            guardian.sign_vaa_body(message)
+        );
+
+        // The query scheme prefixes the message hash.
+        let mut prefixed = QUERY_RESPONSE_PREFIX.to_vec();
+        prefixed.extend_from_slice(&Keccak256::digest(message));
+        let query_digest: [u8; 32] = Keccak256::digest(&prefixed).into();
+        assert_eq!(SigningScheme::Query.digest(message), query_digest);
+
+        let sig = guardian.sign_scheme(SigningScheme::Query, message);
+        assert_eq!(
+            crate::recover_eth_address(&query_digest, sig[1..].try_into().unwrap()),
+            Some(guardian.eth_address)
+        );
+    }
+
     #[test]
     fn test_guardian_set_generate() {
         let set = TestGuardianSet::generate(13, 12345);
@@ -210,6 +820,324 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_has_quorum() {
+        let set = TestGuardianSet::generate(5, 1);
+        let body = b"body";
+
+        // quorum(5) = 4
+        assert!(set.has_quorum(&set.sign_vaa_body_with(body, &[0, 1, 2, 3])));
+        assert!(!set.has_quorum(&set.sign_vaa_body_with(body, &[0, 1, 2])));
+
+        // Duplicates count once.
+        assert!(!set.has_quorum(&set.sign_vaa_body_with(body, &[0, 1, 2, 2])));
+        assert_eq!(
+            distinct_guardian_count(&set.sign_vaa_body_with(body, &[0, 1, 2, 2])),
+            3
+        );
+
+        // Indices outside the set don't count.
+        let larger = TestGuardianSet::generate(7, 2);
+        assert!(!set.has_quorum(&larger.sign_vaa_body_with(body, &[0, 1, 2, 5, 6])));
+    }
+
+    #[test]
+    fn test_has_quorum_gapped_set() {
+        let set = TestGuardianSet::from_indexed(vec![
+            (0, TestGuardian::new([1; 32], 0)),
+            (1, TestGuardian::new([2; 32], 0)),
+            (3, TestGuardian::new([3; 32], 0)),
+        ]);
+        let body = b"body";
+
+        // Four slots, quorum(4) = 3, reached only by counting guardian 3.
+        assert_eq!(set.quorum(), 3);
+        assert!(set.has_quorum(&set.sign_vaa_body_with(body, &[0, 1, 3])));
+        assert!(!set.has_quorum(&set.sign_vaa_body_with(body, &[0, 1])));
+
+        // A gap index is in range, so it counts here but fails recovery.
+        let mut gap = set.sign_vaa_body_with(body, &[0, 1, 3]);
+        gap[2][0] = 2;
+        assert!(set.has_quorum(&gap));
+        assert!(matches!(
+            crate::verify_signatures(&SigningScheme::Vaa.digest(body), &gap, &set.eth_addresses()),
+            Err(crate::VerifyError::KeyMismatch { index: 2, .. })
+        ));
+
+        // Index 4 is past the last slot.
+        let mut past = set.sign_vaa_body_with(body, &[0, 1, 3]);
+        past[2][0] = 4;
+        assert!(!set.has_quorum(&past));
+    }
+
+    #[test]
+    fn test_signatures_equal_unordered() {
+        let set = TestGuardianSet::generate(3, 1);
+        let body = b"body";
+        let sorted = set.sign_vaa_body_with(body, &[0, 1, 2]);
+
+        assert!(signatures_equal_unordered(
+            &sorted,
+            &set.sign_vaa_body_with(body, &[2, 0, 1])
+        ));
+        assert!(!signatures_equal_unordered(
+            &sorted,
+            &set.sign_vaa_body_with(body, &[0, 1])
+        ));
+        assert!(!signatures_equal_unordered(
+            &sorted,
+            &set.sign_vaa_body_with(b"other", &[0, 1, 2])
+        ));
+    }
+
+    #[test]
+    fn test_sign_vaa_body_unsorted() {
+        let set = TestGuardianSet::generate(3, 1);
+        let body = b"body";
+
+        let signatures = set.sign_vaa_body_unsorted(body, &[2, 0, 2]);
+        let indices: Vec<u8> = signatures.iter().map(|sig| sig[0]).collect();
+        assert_eq!(indices, [2, 0, 2]);
+        assert!(matches!(
+            crate::verify_signatures(
+                &SigningScheme::Vaa.digest(body),
+                &signatures,
+                &set.eth_addresses()
+            ),
+            Err(crate::VerifyError::NonIncreasingIndex { position: 1, .. })
+        ));
+
+        // Each signature is the same one the guardian gives in sorted order.
+        let sorted = set.sign_vaa_body(body);
+        assert_eq!(signatures, [sorted[2], sorted[0], sorted[2]]);
+
+        let descending = set.sign_vaa_body_unsorted(body, &[2, 1, 0]);
+        assert_eq!(descending, [sorted[2], sorted[1], sorted[0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "no guardian with index 3 in the set")]
+    fn test_sign_vaa_body_unsorted_unknown_index() {
+        TestGuardianSet::generate(3, 1).sign_vaa_body_unsorted(b"body", &[1, 3]);
+    }
+
+    #[test]
+    fn test_guardian_set_quorum() {
+        for (size, expected) in [(1, 1), (3, 3), (13, 9), (19, 13)] {
+            assert_eq!(TestGuardianSet::generate(size, 1).quorum(), expected);
+        }
+    }
+
+    #[test]
+    fn test_sign_vaa_body_quorum() {
+        let set = TestGuardianSet::generate(19, 1);
+        let body = b"body";
+
+        let signatures = set.sign_vaa_body_quorum(body);
+        assert_eq!(signatures.len(), 13);
+        assert_eq!(
+            signatures,
+            set.sign_vaa_body_with(body, &(0..13).collect::<Vec<u8>>())
+        );
+        assert!(set.has_quorum(&signatures));
+    }
+
+    #[test]
+    fn test_sign_high_s() {
+        // n / 2, big-endian.
+        const HALF_ORDER: [u8; 32] = [
+            0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+            0xff, 0xff, 0x5d, 0x57, 0x6e, 0x73, 0x57, 0xa4, 0x50, 0x1d, 0xdf, 0xe9, 0x2f, 0x46,
+            0x68, 0x1b, 0x20, 0xa0,
+        ];
+        let guardian = TestGuardian::default();
+        let digest = SigningScheme::Vaa.digest(b"body");
+
+        let low = guardian.sign(&digest);
+        let high = guardian.sign_high_s(&digest);
+
+        assert!(low[32..64] <= HALF_ORDER[..]);
+        assert!(high[32..64] > HALF_ORDER[..]);
+        assert_eq!(high[..32], low[..32]);
+        assert_eq!(high[64], low[64] ^ 1);
+        assert_eq!(
+            crate::recover_eth_address(&digest, &high),
+            Some(guardian.eth_address)
+        );
+
+        let wrapped = guardian.with_index(4).sign_vaa_body_high_s(b"body");
+        assert_eq!(wrapped[0], 4);
+        assert_eq!(wrapped[1..], high);
+    }
+
+    #[test]
+    fn test_sign_vaa_body_corrupted() {
+        let set = TestGuardianSet::generate(3, 1);
+        let body = b"body";
+        let digest = SigningScheme::Vaa.digest(body);
+
+        let valid = set.sign_vaa_body(body);
+        let corrupted = set.sign_vaa_body_corrupted(body, &[1]);
+
+        assert_eq!(corrupted[0], valid[0]);
+        assert_eq!(corrupted[2], valid[2]);
+        assert_eq!(corrupted[1][0], 1);
+        assert_ne!(corrupted[1], valid[1]);
+        assert_ne!(
+            crate::recover_eth_address(&digest, &corrupted[1][1..].try_into().unwrap()),
+            Some(set.get(1).unwrap().eth_address)
+        );
+    }
+
+    #[test]
+    fn test_guardian_set_from_account_data_roundtrip() {
+        let set = TestGuardianSet::generate(3, 1);
+        let mut data = set.to_account_data(4);
+        let times = data.len() - 8;
+        data[times..times + 4].copy_from_slice(&1_700_000_000u32.to_le_bytes());
+        data[times + 4..].copy_from_slice(&1_700_086_400u32.to_le_bytes());
+
+        let parsed = TestGuardianSet::from_account_data(&data).unwrap();
+        assert_eq!(parsed.index, 4);
+        assert_eq!(parsed.addresses, set.eth_addresses());
+        assert_eq!(parsed.creation_time, 1_700_000_000);
+        assert_eq!(parsed.expiration_time, 1_700_086_400);
+        assert_eq!(parsed.to_account_data(), data);
+
+        assert!(TestGuardianSet::from_account_data(&data[..data.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_guardian_signature_bytes() {
+        let guardian = TestGuardian::default().with_index(7);
+        let digest = SigningScheme::Vaa.digest(b"body");
+        let signature = guardian.sign(&digest);
+
+        let bytes = guardian_signature_bytes(7, &signature);
+        assert_eq!(bytes[0], 7);
+        assert_eq!(bytes[1..], signature);
+        assert_eq!(bytes, guardian.sign_vaa_body(b"body"));
+    }
+
+    #[test]
+    fn test_assert_signatures_sorted() {
+        let set = TestGuardianSet::generate(3, 1);
+        assert_signatures_sorted(&set.sign_vaa_body(b"body"));
+        assert_signatures_sorted(&[]);
+    }
+
+    #[test]
+    #[should_panic(expected = "at position 2: 1 then 1")]
+    fn test_assert_signatures_sorted_rejects_duplicate() {
+        let set = TestGuardianSet::generate(3, 1);
+        assert_signatures_sorted(&set.sign_vaa_body_with(b"body", &[0, 1, 1]));
+    }
+
+    #[test]
+    fn test_guardian_set_from_indexed_with_gap() {
+        let keys = TestGuardianSet::generate(3, 1);
+        let set = TestGuardianSet::from_indexed(vec![
+            (3, keys.get(2).unwrap().clone()),
+            (0, keys.get(0).unwrap().clone()),
+            (1, keys.get(1).unwrap().clone()),
+        ]);
+
+        // Ordered by index, with the gap padded so slot == index.
+        let addresses = keys.eth_addresses();
+        assert_eq!(
+            set.eth_addresses(),
+            [
+                addresses[0],
+                addresses[1],
+                GAP_GUARDIAN_ADDRESS,
+                addresses[2]
+            ]
+        );
+        assert_eq!(set.get(2).unwrap().index, 3);
+        assert_eq!(set.len(), 3);
+        assert_eq!(
+            set.to_account_data(0),
+            guardian_set_account_data(&set.eth_addresses(), 0, 0)
+        );
+
+        // Signing selects by the explicit index.
+        let sigs = set.sign_vaa_body_with(b"body", &[0, 3]);
+        assert_eq!(sigs.len(), 2);
+        assert_eq!(sigs[1][0], 3);
+        assert!(set.sign_vaa_body_with(b"body", &[2]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "duplicate guardian index 1")]
+    fn test_guardian_set_from_indexed_rejects_duplicates() {
+        TestGuardianSet::from_indexed(vec![
+            (1, TestGuardian::default()),
+            (1, TestGuardian::default()),
+        ]);
+    }
+
+    #[test]
+    fn test_to_account_data_orders() {
+        let set = TestGuardianSet::generate(5, 1);
+        let addresses_at = |data: &[u8]| -> Vec<[u8; 20]> {
+            data[8..8 + 20 * 5]
+                .chunks_exact(20)
+                .map(|a| a.try_into().unwrap())
+                .collect()
+        };
+
+        let data = set.to_account_data(3);
+        assert_eq!(u32::from_le_bytes(data[0..4].try_into().unwrap()), 3);
+        assert_eq!(addresses_at(&data), set.eth_addresses());
+
+        let unsorted = addresses_at(&set.to_account_data_unsorted(3));
+        assert!(unsorted.windows(2).all(|w| w[0] > w[1]));
+        assert_eq!(set.to_account_data_unsorted(3).len(), data.len());
+    }
+
+    #[test]
+    fn test_guardian_set_diff() {
+        let old = TestGuardianSet::generate(4, 1);
+        let replacement = TestGuardianSet::generate(1, 2);
+
+        // Drop guardian 1 and append a new one.
+        let mut guardians: Vec<TestGuardian> = old.iter().cloned().collect();
+        guardians.remove(1);
+        guardians.push(replacement.get(0).unwrap().clone());
+        let new = TestGuardianSet::new(guardians);
+
+        let diff = old.diff(&new);
+        let old_addresses = old.eth_addresses();
+
+        assert_eq!(diff.added, replacement.eth_addresses());
+        assert_eq!(diff.removed, vec![old_addresses[1]]);
+        assert_eq!(
+            diff.retained,
+            vec![old_addresses[0], old_addresses[2], old_addresses[3]]
+        );
+        assert_eq!(old.diff(&old).added, Vec::<[u8; 20]>::new());
+    }
+
+    #[cfg(feature = "random")]
+    #[test]
+    fn test_guardian_set_random() {
+        let a = TestGuardianSet::random(3);
+        let b = TestGuardianSet::random(3);
+
+        assert_eq!(a.len(), 3);
+        assert_eq!(a.get(2).unwrap().index, 2);
+        assert_ne!(a.eth_addresses(), b.eth_addresses());
+    }
+
+    #[test]
+    fn test_guardian_set_with_index() {
+        let set = TestGuardianSet::generate(3, 1);
+        assert_eq!(set.index(), None);
+
+        let set = set.with_index(4);
+        assert_eq!(set.index(), Some(4));
+    }
+
     #[test]
     fn test_sign_with_subset() {
         let set = TestGuardianSet::generate(5, 999);
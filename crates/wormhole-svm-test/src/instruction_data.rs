@@ -0,0 +1,79 @@
+//! Instruction data framing helpers for consumer programs.
+//!
+//! Most programs that accept a VAA body take it either as a borsh `Vec<u8>`
+//! (4-byte little-endian length followed by the bytes) or as an argument of an
+//! Anchor instruction (8-byte discriminator followed by borsh-encoded args).
+//!
+//! # Example
+//!
+//! ```rust
+//! use wormhole_svm_test::instruction_data::length_prefixed;
+//!
+//! // The example verifier expects [discriminator][bump][u32 LE len][body]
+//! let body = vec![1, 2, 3];
+//! let mut data = vec![0, 255];
+//! data.extend(length_prefixed(&body));
+//!
+//! assert_eq!(data, [0, 255, 3, 0, 0, 0, 1, 2, 3]);
+//! ```
+
+/// Encode `bytes` as a borsh `Vec<u8>`: a 4-byte little-endian length
+/// followed by the bytes themselves.
+pub fn length_prefixed(bytes: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(4 + bytes.len());
+    data.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+    data.extend_from_slice(bytes);
+    data
+}
+
+/// Instruction discriminator of the example verifier's `verify_vaa`.
+pub const EXAMPLE_VERIFY_VAA_DISCRIMINATOR: u8 = 0;
+
+/// Build `verify_vaa` instruction data for the `vaa-verifier-example` program.
+///
+/// Matches the example's `build_instruction_data`:
+/// `[discriminator][guardian_set_bump][u32 LE len][body]`. The program takes
+/// the VAA *body*; the signatures travel separately through
+/// `post_signatures`, so no guardian set is needed here.
+pub fn build_example_instruction_data(guardian_set_bump: u8, vaa: &crate::TestVaa) -> Vec<u8> {
+    let mut data = vec![EXAMPLE_VERIFY_VAA_DISCRIMINATOR, guardian_set_bump];
+    data.extend(length_prefixed(&vaa.body()));
+    data
+}
+
+/// Build Anchor-style instruction data: an 8-byte discriminator followed by
+/// the borsh-serialized `args`.
+///
+/// Pass a tuple to encode several arguments in order, e.g.
+/// `anchor_ix_data(disc, (bump, vaa_body))`.
+#[cfg(feature = "borsh")]
+pub fn anchor_ix_data(discriminator: [u8; 8], args: impl borsh::BorshSerialize) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    args.serialize(&mut data)
+        .expect("borsh serialization into a Vec cannot fail");
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_length_prefixed() {
+        assert_eq!(length_prefixed(&[]), [0, 0, 0, 0]);
+        assert_eq!(length_prefixed(&[0xAA; 3]), [3, 0, 0, 0, 0xAA, 0xAA, 0xAA]);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_anchor_ix_data_matches_manual_framing() {
+        let discriminator = [1, 2, 3, 4, 5, 6, 7, 8];
+        let body = vec![0xAB; 5];
+
+        let mut expected = discriminator.to_vec();
+        expected.push(254);
+        expected.extend(length_prefixed(&body));
+
+        assert_eq!(anchor_ix_data(discriminator, (254u8, body)), expected);
+    }
+}
@@ -0,0 +1,166 @@
+//! Fixed VAAs whose signed bytes are pinned, for conformance tests.
+//!
+//! Each [`KnownVector`] is a VAA with documented fields, signed by the Wormhole
+//! devnet (Tilt) guardian, whose key is [`DEFAULT_GUARDIAN_SECRET_KEY`] (see
+//! [`DEVNET_GUARDIAN_ADDRESS`]). Signing is deterministic (RFC 6979 nonces,
+//! low-S), so [`TestVaa::known_vector`] signed with
+//! [`TestGuardianSet::default`] reproduces [`KnownVector::expected_bytes`]
+//! exactly, as does any other implementation that encodes the same fields and
+//! signs with the devnet key. Compare against the constants to check that
+//! body encoding, hashing and signing agree with the reference:
+//!
+//! ```rust
+//! use wormhole_svm_test::{known_vectors::KnownVector, TestGuardianSet, TestVaa};
+//!
+//! let vector = KnownVector::DevnetGuardianSetUpgrade;
+//! let signed = TestVaa::known_vector(vector).sign(&TestGuardianSet::default());
+//! assert_eq!(signed, vector.expected_bytes());
+//! ```
+//!
+//! [`DEFAULT_GUARDIAN_SECRET_KEY`]: crate::DEFAULT_GUARDIAN_SECRET_KEY
+//! [`DEVNET_GUARDIAN_ADDRESS`]: crate::DEVNET_GUARDIAN_ADDRESS
+//! [`TestGuardianSet::default`]: crate::TestGuardianSet::default
+
+use crate::payloads;
+use crate::{emitter_address_from_20, TestVaa, DEVNET_GUARDIAN_ADDRESS};
+
+/// A VAA with pinned signed bytes (see the [module docs](self)).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KnownVector {
+    /// Core Bridge governance upgrade from devnet guardian set 0 to set 1,
+    /// whose only guardian is [`DEVNET_GUARDIAN_ADDRESS`].
+    ///
+    /// Source: the guardian set upgrade layout in the Wormhole monorepo's
+    /// `whitepapers/0002_governance_messaging.md` (see
+    /// [`payloads::guardian_set_upgrade`]), emitted by
+    /// [`payloads::GOVERNANCE_EMITTER`] on [`payloads::GOVERNANCE_CHAIN`] with
+    /// timestamp 1, nonce 0, sequence 1 and consistency level 32. Signed bytes:
+    /// [`DEVNET_GUARDIAN_SET_UPGRADE`].
+    DevnetGuardianSetUpgrade,
+    /// Token Bridge transfer (payload 1) of 1.00000000 of token
+    /// `0x2222…2222` (chain 2) to recipient `0x3333…3333` on Solana, no fee.
+    ///
+    /// Source: the transfer layout in the Wormhole monorepo's
+    /// `whitepapers/0003_token_bridge.md` (see
+    /// [`payloads::token_bridge_transfer`]), emitted by `0x1111…1111` on
+    /// chain 2 with timestamp 1, nonce 0, sequence 1 and consistency level 1.
+    /// Signed bytes: [`DEVNET_TOKEN_TRANSFER`].
+    DevnetTokenTransfer,
+}
+
+impl KnownVector {
+    /// Every known vector.
+    pub const ALL: [KnownVector; 2] = [
+        KnownVector::DevnetGuardianSetUpgrade,
+        KnownVector::DevnetTokenTransfer,
+    ];
+
+    /// The signed VAA bytes for this vector, header and signature included.
+    pub fn expected_bytes(self) -> &'static [u8] {
+        match self {
+            KnownVector::DevnetGuardianSetUpgrade => DEVNET_GUARDIAN_SET_UPGRADE,
+            KnownVector::DevnetTokenTransfer => DEVNET_TOKEN_TRANSFER,
+        }
+    }
+
+    /// Build the unsigned VAA for this vector, as documented on the variant.
+    pub fn vaa(self) -> TestVaa {
+        let (emitter_chain, emitter_address, consistency_level, payload) = match self {
+            KnownVector::DevnetGuardianSetUpgrade => (
+                payloads::GOVERNANCE_CHAIN,
+                payloads::GOVERNANCE_EMITTER,
+                32,
+                payloads::guardian_set_upgrade(1, &[DEVNET_GUARDIAN_ADDRESS]),
+            ),
+            KnownVector::DevnetTokenTransfer => (
+                2,
+                emitter_address_from_20([0x11; 20]),
+                1,
+                payloads::token_bridge_transfer(
+                    100_000_000,
+                    emitter_address_from_20([0x22; 20]),
+                    2,
+                    [0x33; 32],
+                    payloads::SOLANA_CHAIN,
+                    0,
+                ),
+            ),
+        };
+
+        TestVaa {
+            timestamp: 1,
+            nonce: 0,
+            consistency_level,
+            ..TestVaa::new(emitter_chain, emitter_address, 1, payload)
+        }
+    }
+}
+
+/// Signed bytes of [`KnownVector::DevnetGuardianSetUpgrade`].
+pub const DEVNET_GUARDIAN_SET_UPGRADE: &[u8] = &[
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xbb, 0xb9, 0x01, 0x73, 0x44, 0xf5, 0xcf, 0xd4, 0x74,
+    0xd6, 0x68, 0x94, 0x2c, 0x8a, 0x68, 0x3a, 0xf6, 0xf7, 0x33, 0xee, 0x18, 0x3a, 0x9c, 0x3d, 0x9e,
+    0xa2, 0x61, 0x4e, 0x77, 0x7b, 0xa4, 0x1e, 0x58, 0x63, 0x7e, 0x74, 0x38, 0xf4, 0x85, 0x51, 0xde,
+    0x5b, 0x7b, 0x6a, 0x73, 0xe3, 0x66, 0x2b, 0xc9, 0xd5, 0x55, 0xe8, 0xd4, 0x16, 0xe2, 0x68, 0x74,
+    0x29, 0xbd, 0x79, 0x5a, 0xd2, 0x59, 0x8a, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x04, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x43, 0x6f, 0x72, 0x65, 0x02, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x01, 0x01, 0xbe, 0xfa, 0x42, 0x9d, 0x57, 0xcd, 0x18, 0xb7, 0xf8, 0xa4, 0xd9, 0x1a, 0x2d,
+    0xa9, 0xab, 0x4a, 0xf0, 0x5d, 0x0f, 0xbe,
+];
+
+/// Signed bytes of [`KnownVector::DevnetTokenTransfer`].
+pub const DEVNET_TOKEN_TRANSFER: &[u8] = &[
+    0x01, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0xc8, 0x73, 0x0d, 0xd7, 0x23, 0x57, 0xac, 0x7c, 0x9c,
+    0x0b, 0x58, 0x93, 0x13, 0xca, 0x77, 0xa7, 0xce, 0x58, 0x70, 0x99, 0xc4, 0xb1, 0x2c, 0x05, 0xe8,
+    0x81, 0x19, 0xa5, 0x72, 0x40, 0x81, 0x6a, 0x08, 0x17, 0x18, 0x7b, 0xce, 0x59, 0x80, 0x83, 0xea,
+    0xdc, 0x84, 0x4d, 0x62, 0x23, 0x73, 0xb1, 0x0f, 0xb7, 0x7c, 0x05, 0x62, 0x97, 0xd6, 0x63, 0x7c,
+    0xd1, 0x3e, 0x81, 0x43, 0x15, 0x98, 0xf5, 0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x11, 0x11,
+    0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x11,
+    0x11, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x05, 0xf5, 0xe1, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22,
+    0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x22, 0x00, 0x02, 0x33, 0x33,
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33,
+    0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x00, 0x01,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{verify_vaa_against_account, TestGuardianSet};
+
+    #[test]
+    fn test_known_vectors_match_expected_bytes() {
+        let devnet = TestGuardianSet::default();
+        for vector in KnownVector::ALL {
+            let signed = TestVaa::known_vector(vector).sign(&devnet);
+            assert_eq!(signed, vector.expected_bytes(), "{:?}", vector);
+            verify_vaa_against_account(&signed, &devnet.to_account_data(0))
+                .unwrap_or_else(|e| panic!("{:?}: {}", vector, e));
+        }
+    }
+
+    #[test]
+    fn test_known_vector_payloads() {
+        let upgrade = KnownVector::DevnetGuardianSetUpgrade.expected_bytes();
+        // header (6) + one signature (66) + body header (51)
+        let payload = &upgrade[6 + 66 + 51..];
+        assert_eq!(&payload[..32], &payloads::CORE_MODULE);
+        assert_eq!(&payload[payload.len() - 20..], &DEVNET_GUARDIAN_ADDRESS);
+
+        let transfer = KnownVector::DevnetTokenTransfer.expected_bytes();
+        assert_eq!(transfer.len(), 6 + 66 + 51 + 133);
+        assert_eq!(
+            transfer[6 + 66 + 51],
+            payloads::TOKEN_BRIDGE_TRANSFER_PAYLOAD_ID
+        );
+    }
+}
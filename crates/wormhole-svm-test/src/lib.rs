@@ -21,10 +21,33 @@
 //! ```
 
 mod guardian;
+pub mod instruction_data;
+pub mod known_vectors;
+pub mod malformed;
+pub mod negative_fixtures;
+pub mod payloads;
+mod scenario;
 mod vaa;
+mod verify;
 
 pub use guardian::*;
+pub use scenario::*;
 pub use vaa::*;
+pub use verify::*;
+
+/// The secp256k1 implementation behind guardian keys and signatures.
+///
+/// Name types such as [`TestGuardian::public_key`]'s through this re-export
+/// rather than a direct `libsecp256k1` dependency, so they always match the
+/// version this crate signs with:
+///
+/// ```rust
+/// use wormhole_svm_test::{secp256k1::PublicKey, TestGuardian};
+///
+/// let public_key: PublicKey = TestGuardian::default().public_key;
+/// assert_eq!(public_key.serialize().len(), 65);
+/// ```
+pub use libsecp256k1 as secp256k1;
 
 #[cfg(feature = "litesvm")]
 mod litesvm;
@@ -37,3 +60,9 @@ mod resolver;
 
 #[cfg(feature = "resolver")]
 pub use resolver::*;
+
+#[cfg(feature = "serde")]
+mod test_vector;
+
+#[cfg(feature = "serde")]
+pub use test_vector::*;
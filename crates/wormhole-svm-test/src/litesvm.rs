@@ -1,16 +1,24 @@
 //! LiteSVM helpers for setting up Wormhole test environments.
 
-use std::path::PathBuf;
+use std::{borrow::Cow, collections::BTreeMap, path::PathBuf};
 
+pub use agave_feature_set::FeatureSet;
 use litesvm::LiteSVM;
 use solana_sdk::{
     account::Account,
+    address_lookup_table::{
+        self,
+        state::{AddressLookupTable, LookupTableMeta},
+        AddressLookupTableAccount,
+    },
     hash::Hash,
     instruction::Instruction,
+    message::{v0, VersionedMessage},
+    packet::PACKET_DATA_SIZE,
     pubkey::Pubkey,
     rent::Rent,
     signature::{Keypair, Signature, Signer},
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
 use thiserror::Error;
 use wormhole_svm_definitions::{
@@ -51,6 +59,10 @@ pub enum WormholeTestError {
     IoError(#[from] std::io::Error),
     #[error("Failed to load program: {0}")]
     LoadError(String),
+    #[error("Transaction failed: {0}")]
+    TransactionFailed(String),
+    #[error("Failed to build transaction: {0}")]
+    TransactionBuild(String),
     #[error("VAA verification bypass detected: {0}")]
     VerificationBypass(String),
     #[error("Emitter chain check missing: {0}")]
@@ -61,12 +73,29 @@ pub enum WormholeTestError {
     ReplayProtectionMissing(String),
     #[error("Submit error: {0}")]
     SubmitError(#[from] wormhole_svm_submit::SubmitError),
+    #[error("Guardian set is associated with index {set} but was installed at index {requested}")]
+    GuardianSetIndexMismatch { set: u32, requested: u32 },
+    #[error("Transaction too large: {size} bytes exceeds the {max}-byte packet limit")]
+    TransactionTooLarge { size: usize, max: usize },
+    #[error("Invalid guardian signatures: {0}")]
+    InvalidSignatures(#[from] crate::VerifyError),
+    #[error("Account not found: {0}")]
+    AccountNotFound(Pubkey),
+    #[error("Guardian {index} does not fit in the Core Bridge's {max} signer slots")]
+    SignerIndexOutOfRange { index: u8, max: usize },
+    #[error("Failed to deserialize account {pubkey}: {reason}")]
+    AccountDeserialize { pubkey: Pubkey, reason: String },
 }
 
 // ReplayProtection is defined in vaa.rs and re-exported from the crate root.
 
 /// Configuration for loading Wormhole programs.
-#[derive(Default)]
+///
+/// Marked `#[non_exhaustive]` so options can be added without breaking
+/// callers: start from `WormholeProgramsConfig::default()` and assign fields
+/// or use the `with_*` methods.
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
 pub struct WormholeProgramsConfig {
     /// Path to verify_vaa_shim.so (or None to search default locations).
     pub verify_vaa_shim: Option<PathBuf>,
@@ -74,6 +103,77 @@ pub struct WormholeProgramsConfig {
     pub core_bridge: Option<PathBuf>,
     /// Path to post_message_shim.so (or None to search default locations).
     pub post_message_shim: Option<PathBuf>,
+    /// Runtime features of the SVM built by [`setup_wormhole_svm`].
+    ///
+    /// Functions that take an existing `LiteSVM` ignore this; its features
+    /// were fixed when it was built (see [`new_svm`]).
+    pub features: FeatureConfig,
+}
+
+impl WormholeProgramsConfig {
+    /// Set the runtime features of the SVM built by [`setup_wormhole_svm`].
+    pub fn with_features(mut self, features: FeatureConfig) -> Self {
+        self.features = features;
+        self
+    }
+}
+
+/// Which runtime features a LiteSVM instance activates.
+///
+/// The Verify VAA Shim recovers guardian signers with the
+/// `sol_secp256k1_recover` syscall rather than the Secp256k1 precompile
+/// program, so there is no verification backend to select: the same path runs
+/// whether or not the precompile is loaded or its features are active.
+#[derive(Clone, Debug, Default)]
+pub enum FeatureConfig {
+    /// Whatever `LiteSVM::new()` activates (currently every feature).
+    #[default]
+    LiteSvmDefault,
+    /// Exactly this feature set, e.g. a copy of mainnet's activations, to run
+    /// the program under test with the features a real cluster has.
+    Custom(FeatureSet),
+}
+
+/// Lamports `LiteSVM::new()` mints into its initial supply.
+const LITESVM_DEFAULT_LAMPORTS: u64 = 1_000_000 * solana_sdk::native_token::LAMPORTS_PER_SOL;
+
+/// Build a LiteSVM instance with the given runtime features.
+///
+/// A custom feature set has to be in place before the builtins and
+/// precompiles are added, since which ones load depends on it, so this
+/// repeats `LiteSVM::new()`'s builder steps with the feature set swapped
+/// rather than overriding it afterwards.
+pub fn new_svm(features: &FeatureConfig) -> LiteSVM {
+    match features {
+        FeatureConfig::LiteSvmDefault => LiteSVM::new(),
+        FeatureConfig::Custom(feature_set) => LiteSVM::default()
+            .with_feature_set(feature_set.clone())
+            .with_builtins()
+            .with_lamports(LITESVM_DEFAULT_LAMPORTS)
+            .with_sysvars()
+            .with_precompiles()
+            .with_default_programs()
+            .with_sigverify(true)
+            .with_blockhash_check(true),
+    }
+}
+
+/// Which Core Bridge state accounts [`setup_wormhole_accounts_only`] writes
+/// besides the guardian set.
+pub struct WormholeAccountsConfig {
+    /// Write the bridge config account (needed to verify and post messages).
+    pub bridge_config: bool,
+    /// Write the fee collector account (needed to post messages).
+    pub fee_collector: bool,
+}
+
+impl Default for WormholeAccountsConfig {
+    fn default() -> Self {
+        Self {
+            bridge_config: true,
+            fee_collector: true,
+        }
+    }
 }
 
 /// Accounts created by setup_wormhole.
@@ -84,6 +184,19 @@ pub struct WormholeAccounts {
     pub guardian_set_bump: u8,
 }
 
+impl WormholeAccounts {
+    /// A bump that does not derive [`guardian_set`](Self::guardian_set).
+    ///
+    /// Pass it as the `guardian_set_bump` of a verify instruction to check that
+    /// the bump is actually validated: the Verify VAA Shim re-derives the
+    /// guardian set PDA from the index and the given bump, and
+    /// `verify_hash` must fail because the result is not the guardian set
+    /// account passed in (or not a valid PDA at all).
+    pub fn non_canonical_bump(&self) -> u8 {
+        self.guardian_set_bump.wrapping_add(1)
+    }
+}
+
 const PROGRAM_NOT_FOUND_HELP: &str = r#"Wormhole program binaries not found.
 
 Enable the `bundled-fixtures` feature to use pre-bundled mainnet binaries:
@@ -223,8 +336,21 @@ pub fn create_guardian_set_account(
     guardians: &TestGuardianSet,
     index: u32,
 ) -> (Pubkey, u8) {
-    let (address, bump) = find_guardian_set_address(index.to_be_bytes(), &CORE_BRIDGE_PROGRAM_ID);
-    let data = build_guardian_set_data(guardians, index);
+    create_guardian_set_account_with_expiration(svm, guardians, index, 0)
+}
+
+/// Create a guardian set account in LiteSVM that expires at `expiration_time`.
+///
+/// An `expiration_time` of 0 means the set never expires. Returns the PDA
+/// address and bump of the created account.
+pub fn create_guardian_set_account_with_expiration(
+    svm: &mut LiteSVM,
+    guardians: &TestGuardianSet,
+    index: u32,
+    expiration_time: u32,
+) -> (Pubkey, u8) {
+    let (address, bump) = derive_guardian_set(index);
+    let data = build_guardian_set_data_with_expiration(guardians, index, expiration_time);
 
     let rent = Rent::default();
     let lamports = rent.minimum_balance(data.len());
@@ -289,6 +415,43 @@ pub fn build_bridge_fee_ix(payer: &Pubkey) -> Instruction {
     solana_sdk::system_instruction::transfer(payer, &CORE_BRIDGE_FEE_COLLECTOR, DEFAULT_BRIDGE_FEE)
 }
 
+/// Derive the Core Bridge guardian set PDA and bump for `guardian_set_index`.
+///
+/// Seeds are `["GuardianSet", index (big-endian)]` under the Core Bridge
+/// program ID; this is where [`setup_wormhole`] installs the set.
+pub fn derive_guardian_set(guardian_set_index: u32) -> (Pubkey, u8) {
+    find_guardian_set_address(guardian_set_index.to_be_bytes(), &CORE_BRIDGE_PROGRAM_ID)
+}
+
+/// Assert that `wormhole` holds the canonical guardian set PDA and bump for
+/// `guardian_set_index`.
+///
+/// # Panics
+///
+/// Panics with both addresses (and bumps) if they differ from
+/// [`derive_guardian_set`].
+pub fn assert_guardian_set_derived(wormhole: &WormholeAccounts, guardian_set_index: u32) {
+    let (expected, expected_bump) = derive_guardian_set(guardian_set_index);
+    assert!(
+        wormhole.guardian_set == expected && wormhole.guardian_set_bump == expected_bump,
+        "guardian set {} is at {} (bump {}), expected {} (bump {})",
+        guardian_set_index,
+        wormhole.guardian_set,
+        wormhole.guardian_set_bump,
+        expected,
+        expected_bump
+    );
+}
+
+/// Derive the Post Message Shim's event authority PDA and bump.
+///
+/// Programs that CPI into the shim's `post_message` must pass this account
+/// (seeds `["__event_authority"]` under the shim program ID), followed by the
+/// shim program itself.
+pub fn derive_post_message_event_authority() -> (Pubkey, u8) {
+    wormhole_svm_definitions::find_event_authority_address(&POST_MESSAGE_SHIM_PROGRAM_ID)
+}
+
 /// Create the Wormhole fee collector account in LiteSVM.
 ///
 /// The fee collector is needed for posting Wormhole messages.
@@ -308,6 +471,23 @@ pub fn create_fee_collector(svm: &mut LiteSVM) {
     svm.set_account(CORE_BRIDGE_FEE_COLLECTOR, account).unwrap();
 }
 
+/// Fail if `guardians` carries an index (see [`TestGuardianSet::with_index`])
+/// other than `guardian_set_index`.
+fn check_set_index(
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+) -> Result<(), WormholeTestError> {
+    match guardians.index() {
+        Some(set) if set != guardian_set_index => {
+            Err(WormholeTestError::GuardianSetIndexMismatch {
+                set,
+                requested: guardian_set_index,
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
 /// Set up Wormhole in an existing LiteSVM instance.
 ///
 /// This is a convenience function that:
@@ -315,19 +495,82 @@ pub fn create_fee_collector(svm: &mut LiteSVM) {
 /// 2. Creates a guardian set account
 /// 3. Creates a bridge config account (with full support for message posting)
 /// 4. Creates the fee collector account
+///
+/// If `guardians` carries an index (see [`TestGuardianSet::with_index`]), it
+/// must equal `guardian_set_index`.
 pub fn setup_wormhole(
     svm: &mut LiteSVM,
     guardians: &TestGuardianSet,
     guardian_set_index: u32,
     config: WormholeProgramsConfig,
 ) -> Result<WormholeAccounts, WormholeTestError> {
+    check_set_index(guardians, guardian_set_index)?;
+
     load_wormhole_programs(svm, config)?;
 
+    setup_wormhole_accounts_only(
+        svm,
+        guardians,
+        guardian_set_index,
+        WormholeAccountsConfig::default(),
+    )
+}
+
+/// Build a LiteSVM instance with `config.features` and set up Wormhole in it.
+///
+/// Equivalent to [`new_svm`] followed by [`setup_wormhole`].
+pub fn setup_wormhole_svm(
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+    config: WormholeProgramsConfig,
+) -> Result<(LiteSVM, WormholeAccounts), WormholeTestError> {
+    let mut svm = new_svm(&config.features);
+    let accounts = setup_wormhole(&mut svm, guardians, guardian_set_index, config)?;
+    Ok((svm, accounts))
+}
+
+/// Set up Wormhole with `guardians` installed at the index in `vaa`'s header.
+///
+/// Verifies `vaa` offline against `guardians` first (see
+/// [`TestVaa::preflight`](crate::TestVaa::preflight)) and touches the SVM only
+/// if it passes, then calls [`setup_wormhole`] with
+/// [`vaa.guardian_set_index`](crate::TestVaa::guardian_set_index). Deriving
+/// the index from the VAA keeps the installed set and the VAA under test from
+/// drifting apart.
+pub fn setup_for_vaa(
+    svm: &mut LiteSVM,
+    vaa: &crate::TestVaa,
+    guardians: &TestGuardianSet,
+    config: WormholeProgramsConfig,
+) -> Result<WormholeAccounts, WormholeTestError> {
+    vaa.verify_offline(guardians)?;
+
+    setup_wormhole(svm, guardians, vaa.guardian_set_index, config)
+}
+
+/// Write Wormhole state accounts into a LiteSVM instance without loading programs.
+///
+/// Use this when the Wormhole programs are already loaded elsewhere (e.g. by a
+/// shared fixture or another test framework). Creates the guardian set account
+/// and, per `config`, the bridge config and fee collector. Returns the same
+/// accounts as [`setup_wormhole`], with the same guardian set index check.
+pub fn setup_wormhole_accounts_only(
+    svm: &mut LiteSVM,
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+    config: WormholeAccountsConfig,
+) -> Result<WormholeAccounts, WormholeTestError> {
+    check_set_index(guardians, guardian_set_index)?;
+
     let (guardian_set, guardian_set_bump) =
         create_guardian_set_account(svm, guardians, guardian_set_index);
 
-    create_bridge_config(svm, guardian_set_index);
-    create_fee_collector(svm);
+    if config.bridge_config {
+        create_bridge_config(svm, guardian_set_index);
+    }
+    if config.fee_collector {
+        create_fee_collector(svm);
+    }
 
     Ok(WormholeAccounts {
         guardian_set,
@@ -335,6 +578,192 @@ pub fn setup_wormhole(
     })
 }
 
+/// A guardian set installed by [`setup_wormhole_expiring`].
+pub struct ExpiringGuardianSet {
+    /// The installed guardian set accounts.
+    pub accounts: WormholeAccounts,
+    /// The guardian set index.
+    pub index: u32,
+    /// Unix timestamp the set expires at (as written into the account).
+    pub expiration_time: u32,
+}
+
+/// Set up Wormhole with a guardian set that expires `valid_for_seconds` from now.
+///
+/// Like [`setup_wormhole`], but the guardian set's `expiration_time` is the
+/// current clock's `unix_timestamp + valid_for_seconds`. VAAs signed by the set
+/// verify until [`expire_now`] advances the clock past that time, which models
+/// a rotated-out set reaching the end of its grace period within one test.
+pub fn setup_wormhole_expiring(
+    svm: &mut LiteSVM,
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+    valid_for_seconds: u32,
+    config: WormholeProgramsConfig,
+) -> Result<ExpiringGuardianSet, WormholeTestError> {
+    use solana_sdk::clock::Clock;
+
+    setup_wormhole(svm, guardians, guardian_set_index, config)?;
+
+    let now = svm.get_sysvar::<Clock>().unix_timestamp;
+    let expiration_time = u32::try_from(now)
+        .unwrap_or(0)
+        .saturating_add(valid_for_seconds);
+
+    let (guardian_set, guardian_set_bump) = create_guardian_set_account_with_expiration(
+        svm,
+        guardians,
+        guardian_set_index,
+        expiration_time,
+    );
+
+    Ok(ExpiringGuardianSet {
+        accounts: WormholeAccounts {
+            guardian_set,
+            guardian_set_bump,
+        },
+        index: guardian_set_index,
+        expiration_time,
+    })
+}
+
+/// Advance the clock one second past the set's expiration time.
+///
+/// After this, verifying against the set fails the expiration check.
+pub fn expire_now(svm: &mut LiteSVM, set: &ExpiringGuardianSet) {
+    use solana_sdk::clock::Clock;
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = set.expiration_time as i64 + 1;
+    svm.set_sysvar(&clock);
+}
+
+/// Two guardian sets installed by [`setup_wormhole_rotated`].
+pub struct RotatedGuardianSets {
+    /// The previous set, still valid until its grace period ends.
+    pub previous: ExpiringGuardianSet,
+    /// The current set, which never expires.
+    pub current: WormholeAccounts,
+    /// The current guardian set index (also the bridge config's index).
+    pub current_index: u32,
+}
+
+/// Set up Wormhole as it looks just after a guardian set rotation.
+///
+/// Installs `previous` at `previous_index` with an expiration time
+/// `grace_period_seconds` from now, and `current` at `current_index` with no
+/// expiration, pointing the bridge config at `current_index`. VAAs signed by
+/// the previous set verify until [`expire_now`] is called on
+/// [`RotatedGuardianSets::previous`]; VAAs signed by the current set verify
+/// throughout.
+pub fn setup_wormhole_rotated(
+    svm: &mut LiteSVM,
+    previous: &TestGuardianSet,
+    previous_index: u32,
+    current: &TestGuardianSet,
+    current_index: u32,
+    grace_period_seconds: u32,
+    config: WormholeProgramsConfig,
+) -> Result<RotatedGuardianSets, WormholeTestError> {
+    let previous =
+        setup_wormhole_expiring(svm, previous, previous_index, grace_period_seconds, config)?;
+
+    let current_accounts = setup_wormhole_accounts_only(
+        svm,
+        current,
+        current_index,
+        WormholeAccountsConfig {
+            bridge_config: true,
+            fee_collector: false,
+        },
+    )?;
+
+    Ok(RotatedGuardianSets {
+        previous,
+        current: current_accounts,
+        current_index,
+    })
+}
+
+/// One guardian set to install with [`setup_wormhole_multi`].
+#[derive(Clone, Copy)]
+pub struct GuardianSetSpec<'a> {
+    /// The guardian set index.
+    pub index: u32,
+    /// The guardians in the set.
+    pub guardians: &'a TestGuardianSet,
+    /// Expiration time written into the account (0 = never expires).
+    pub expiration_time: u32,
+}
+
+impl<'a> GuardianSetSpec<'a> {
+    /// A set at `index` that never expires.
+    pub fn new(index: u32, guardians: &'a TestGuardianSet) -> Self {
+        Self {
+            index,
+            guardians,
+            expiration_time: 0,
+        }
+    }
+
+    /// Expire the set at `expiration_time` (a Unix timestamp).
+    pub fn expiring_at(mut self, expiration_time: u32) -> Self {
+        self.expiration_time = expiration_time;
+        self
+    }
+}
+
+impl<'a> From<(u32, &'a TestGuardianSet)> for GuardianSetSpec<'a> {
+    fn from((index, guardians): (u32, &'a TestGuardianSet)) -> Self {
+        Self::new(index, guardians)
+    }
+}
+
+/// Set up Wormhole with several guardian sets installed at once.
+///
+/// Loads the programs, writes one Core Bridge guardian set PDA per entry in
+/// `sets` (each with its own expiration time), and points the bridge config
+/// at the highest index, as after a series of upgrades. Returns each set's
+/// `(address, bump)` keyed by index. Use it to check that a VAA signed by set
+/// N is rejected against set N + 1, or that an expired set is rejected.
+///
+/// Every set that carries an index (see [`TestGuardianSet::with_index`]) must
+/// be installed at that index.
+pub fn setup_wormhole_multi(
+    svm: &mut LiteSVM,
+    sets: &[GuardianSetSpec],
+    config: WormholeProgramsConfig,
+) -> Result<BTreeMap<u32, (Pubkey, u8)>, WormholeTestError> {
+    let Some(current_index) = sets.iter().map(|set| set.index).max() else {
+        return Err(WormholeTestError::LoadError(
+            "setup_wormhole_multi needs at least one guardian set".to_string(),
+        ));
+    };
+    for spec in sets {
+        check_set_index(spec.guardians, spec.index)?;
+    }
+
+    load_wormhole_programs(svm, config)?;
+
+    let installed = sets
+        .iter()
+        .map(|spec| {
+            let account = create_guardian_set_account_with_expiration(
+                svm,
+                spec.guardians,
+                spec.index,
+                spec.expiration_time,
+            );
+            (spec.index, account)
+        })
+        .collect();
+
+    create_bridge_config(svm, current_index);
+    create_fee_collector(svm);
+
+    Ok(installed)
+}
+
 /// Build guardian set account data.
 ///
 /// Format (from Wormhole core bridge):
@@ -344,26 +773,19 @@ pub fn setup_wormhole(
 /// - creation_time: u32 (4 bytes, little-endian)
 /// - expiration_time: u32 (4 bytes, little-endian) - 0 means never expires
 pub fn build_guardian_set_data(guardians: &TestGuardianSet, index: u32) -> Vec<u8> {
-    let mut data = Vec::new();
-
-    // Guardian set index
-    data.extend_from_slice(&index.to_le_bytes());
-
-    // Number of keys
-    data.extend_from_slice(&(guardians.len() as u32).to_le_bytes());
-
-    // Guardian Ethereum addresses
-    for addr in guardians.eth_addresses() {
-        data.extend_from_slice(&addr);
-    }
-
-    // Creation time (0 for testing)
-    data.extend_from_slice(&0u32.to_le_bytes());
-
-    // Expiration time (0 = never expires)
-    data.extend_from_slice(&0u32.to_le_bytes());
+    build_guardian_set_data_with_expiration(guardians, index, 0)
+}
 
-    data
+/// Build guardian set account data with an explicit expiration time.
+///
+/// Same layout as [`build_guardian_set_data`]; `expiration_time` is written
+/// verbatim (0 means never expires).
+pub fn build_guardian_set_data_with_expiration(
+    guardians: &TestGuardianSet,
+    index: u32,
+    expiration_time: u32,
+) -> Vec<u8> {
+    crate::guardian::guardian_set_account_data(&guardians.eth_addresses(), index, expiration_time)
 }
 
 // =============================================================================
@@ -438,6 +860,143 @@ pub fn post_signatures(
     .map_err(WormholeTestError::from)
 }
 
+/// Post guardian signatures, optionally validating them against a guardian set first.
+///
+/// With `validate_against: Some(set)`, fails before sending anything if:
+/// - `set` is associated with an index (see [`TestGuardianSet::with_index`])
+///   other than `guardian_set_index`
+/// - any signature's guardian index is out of range for `set` (not below the
+///   length of [`TestGuardianSet::eth_addresses`])
+///
+/// This catches fixtures signed by the wrong set at post time instead of as
+/// an on-chain error during verification. With `None` it behaves exactly like
+/// [`post_signatures`].
+pub fn post_signatures_checked(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+    validate_against: Option<&TestGuardianSet>,
+) -> Result<PostedSignatures, WormholeTestError> {
+    if let Some(guardians) = validate_against {
+        check_set_index(guardians, guardian_set_index)?;
+
+        let slots = guardians.eth_addresses().len();
+        for (position, sig) in signatures.iter().enumerate() {
+            if sig[0] as usize >= slots {
+                return Err(crate::VerifyError::IndexOutOfRange {
+                    position,
+                    index: sig[0],
+                    guardians: slots,
+                }
+                .into());
+            }
+        }
+    }
+
+    post_signatures(svm, payer, guardian_set_index, signatures)
+}
+
+/// Post guardian signatures with rent paid by a separate `funder`.
+///
+/// `payer` pays the transaction fee while `funder` is the instruction's payer
+/// account and provides the lamports for the signatures account. Both sign the
+/// transaction. Useful for testing fee-sponsorship setups where the rent payer
+/// is not the fee payer.
+pub fn post_signatures_funded(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    funder: &Keypair,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+) -> Result<PostedSignatures, WormholeTestError> {
+    let guardian_sigs_keypair = Keypair::new();
+
+    let ix = build_post_signatures_ix(
+        &funder.pubkey(),
+        &guardian_sigs_keypair.pubkey(),
+        guardian_set_index,
+        signatures,
+    );
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, funder, &guardian_sigs_keypair],
+        blockhash,
+    );
+
+    svm.send_transaction(tx).map_err(|e| {
+        WormholeTestError::TransactionFailed(format!("post_signatures_funded: {:?}", e))
+    })?;
+
+    let pubkey = guardian_sigs_keypair.pubkey();
+    Ok(PostedSignatures {
+        keypair: guardian_sigs_keypair,
+        pubkey,
+    })
+}
+
+/// Post several batches of guardian signatures, one signatures account per batch.
+///
+/// Returns the posted accounts in the same order as `batches`.
+pub fn post_signatures_batch(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guardian_set_index: u32,
+    batches: &[Vec<[u8; 66]>],
+) -> Result<Vec<PostedSignatures>, WormholeTestError> {
+    batches
+        .iter()
+        .map(|signatures| post_signatures(svm, payer, guardian_set_index, signatures))
+        .collect()
+}
+
+/// Post `signatures`, then post them again to the same signatures account.
+///
+/// Returns the first posting and the result of the second, so a test can pin
+/// down the shim's duplicate-post semantics. The shim treats a post to an
+/// existing account as an *append* by the original payer, but the account is
+/// sized by the first post's declared `total_signatures`, which
+/// [`post_signatures`] sets to exactly `signatures.len()`. The second post
+/// therefore has no room and fails, leaving the first posting intact and
+/// still usable for verification.
+///
+/// # Errors
+///
+/// Fails only if the first post fails; the second post's outcome is the
+/// returned `Result`.
+pub fn post_signatures_twice(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+) -> Result<(PostedSignatures, Result<(), WormholeTestError>), WormholeTestError> {
+    let posted = post_signatures(svm, payer, guardian_set_index, signatures)?;
+    // The second transaction is otherwise identical to the first, and LiteSVM
+    // would reject it as already processed.
+    svm.expire_blockhash();
+
+    let ix = build_post_signatures_ix(
+        &payer.pubkey(),
+        &posted.pubkey,
+        guardian_set_index,
+        signatures,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, &posted.keypair],
+        svm.latest_blockhash(),
+    );
+    let second = svm.send_transaction(tx).map(|_| ()).map_err(|e| {
+        WormholeTestError::TransactionFailed(format!("second post_signatures: {:?}", e))
+    });
+
+    Ok((posted, second))
+}
+
 /// Close a guardian signatures account to reclaim rent.
 ///
 /// The refund is sent to the specified recipient.
@@ -457,11 +1016,32 @@ pub fn close_signatures(
     let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
 
     svm.send_transaction(tx)
-        .map_err(|e| WormholeTestError::LoadError(format!("close_signatures failed: {:?}", e)))?;
+        .map_err(|e| WormholeTestError::TransactionFailed(format!("close_signatures: {:?}", e)))?;
 
     Ok(())
 }
 
+/// Close several guardian signatures accounts, refunding each to `refund_recipient`.
+///
+/// Returns the total lamports reclaimed (the sum of the accounts' balances
+/// before closing), for comparison against the rent paid by
+/// [`post_signatures_batch`].
+pub fn close_signatures_batch(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    signatures_accounts: &[Pubkey],
+    refund_recipient: &Pubkey,
+) -> Result<u64, WormholeTestError> {
+    let mut reclaimed = 0;
+    for pubkey in signatures_accounts {
+        reclaimed += svm
+            .get_account(pubkey)
+            .map_or(0, |account| account.lamports);
+        close_signatures(svm, payer, pubkey, refund_recipient)?;
+    }
+    Ok(reclaimed)
+}
+
 /// Execute a closure with posted signatures, automatically handling post and close.
 ///
 /// This is a "bracket" pattern that:
@@ -501,7 +1081,7 @@ where
 
     // Step 2: Run user's closure
     let result = f(svm, &posted.pubkey)
-        .map_err(|e| WormholeTestError::LoadError(format!("user closure failed: {}", e)))?;
+        .map_err(|e| WormholeTestError::TransactionFailed(format!("user closure: {}", e)))?;
 
     // Step 3: Close signatures account
     close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())?;
@@ -653,7 +1233,7 @@ where
 
     // Run closure on original SVM with correct signatures
     let result = f(svm, &posted.pubkey, &vaa_body)
-        .map_err(|e| WormholeTestError::LoadError(format!("VAA verification failed: {}", e)))?;
+        .map_err(|e| WormholeTestError::TransactionFailed(format!("VAA verification: {}", e)))?;
 
     close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())?;
 
@@ -692,72 +1272,762 @@ where
     Ok(result)
 }
 
-/// Execute a closure that verifies a VAA, WITHOUT automatic verification check.
+/// Verify the same VAA in two separate transactions and return both results.
 ///
-/// This is the unchecked version of [`with_vaa`] that skips the automatic negative
-/// test. Use this only when you have a specific reason to skip the safety check,
-/// such as testing error handling paths or when you need full control over execution.
+/// Posts the guardian signatures, runs `f`, and closes the signatures account;
+/// then does the same again with a fresh signatures account. Both attempts run
+/// on `svm` itself, so state written by the first attempt is visible to the
+/// second. The signatures account is closed after each attempt whether or not
+/// `f` succeeded.
 ///
-/// **Prefer [`with_vaa`] in most cases** - it automatically ensures your program
-/// actually verifies VAAs.
+/// Replay semantics are program-specific: a program with replay protection
+/// should reject the second attempt, while an intentionally idempotent one may
+/// accept it. Assert on the returned pair accordingly. [`with_vaa`] runs the
+/// rejection variant of this check automatically.
 ///
 /// # Example
 ///
 /// ```ignore
-/// // Only use this if you have a specific reason to skip the safety check
-/// with_vaa_unchecked(&mut svm, &payer, &guardians, 0, &vaa, |svm, sigs_pubkey, vaa_body| {
+/// let (first, second) = verify_vaa_twice(&mut svm, &payer, &guardians, 0, &vaa, |svm, sigs, body| {
 ///     let tx = Transaction::new_signed_with_payer(...);
-///     svm.send_transaction(tx).map_err(|e| format!("{:?}", e))
+///     svm.send_transaction(tx)
 /// })?;
+/// assert!(first.is_ok());
+/// assert!(second.is_err(), "replayed VAA must be rejected");
 /// ```
-pub fn with_vaa_unchecked<F, T, E>(
+pub fn verify_vaa_twice<F, T, E>(
     svm: &mut LiteSVM,
     payer: &Keypair,
     guardians: &TestGuardianSet,
     guardian_set_index: u32,
     vaa: &crate::TestVaa,
-    f: F,
-) -> Result<T, WormholeTestError>
+    mut f: F,
+) -> Result<(Result<T, E>, Result<T, E>), WormholeTestError>
 where
-    F: FnOnce(&mut LiteSVM, &Pubkey, &[u8]) -> Result<T, E>,
-    E: std::fmt::Display,
+    F: FnMut(&mut LiteSVM, &Pubkey, &[u8]) -> Result<T, E>,
 {
     let vaa_body = vaa.body();
     let signatures = vaa.guardian_signatures(guardians);
 
-    let posted = post_signatures(svm, payer, guardian_set_index, &signatures)?;
-
-    let result = f(svm, &posted.pubkey, &vaa_body)
-        .map_err(|e| WormholeTestError::LoadError(format!("closure failed: {}", e)))?;
+    let mut attempt = |svm: &mut LiteSVM| -> Result<Result<T, E>, WormholeTestError> {
+        let posted = post_signatures(svm, payer, guardian_set_index, &signatures)?;
+        let result = f(svm, &posted.pubkey, &vaa_body);
+        close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())?;
+        Ok(result)
+    };
 
-    close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())?;
+    let first = attempt(svm)?;
+    // Advance the blockhash so an identical second transaction isn't
+    // rejected as a duplicate of the first.
+    svm.expire_blockhash();
+    let second = attempt(svm)?;
 
-    Ok(result)
+    Ok((first, second))
 }
 
-/// Build a post_signatures instruction without sending it.
+/// Assert that verifying `vaa_bytes` with `signatures` is rejected.
 ///
-/// Useful if you need to combine this with other instructions in a single transaction.
-pub fn build_post_signatures_ix(
-    payer: &Pubkey,
-    guardian_signatures_keypair: &Pubkey,
-    guardian_set_index: u32,
-    signatures: &[[u8; 66]],
-) -> Instruction {
-    wormhole_svm_submit::build_post_signatures_ix(
-        payer,
-        guardian_signatures_keypair,
-        &VERIFY_VAA_SHIM_PROGRAM_ID,
-        guardian_set_index,
-        signatures,
-    )
-}
-
-/// Build a close_signatures instruction without sending it.
+/// Posts `signatures` under the guardian set index in the VAA header, builds
+/// the verify instruction with `build_verify_ix(signatures_pubkey, vaa_body)`,
+/// sends it, and closes the signatures account again. Returns the failed
+/// transaction so the caller can inspect the error or logs.
 ///
-/// Useful if you need to combine this with other instructions in a single transaction.
-pub fn build_close_signatures_ix(
-    guardian_signatures: &Pubkey,
+/// Pair it with [`negative_fixtures`](crate::negative_fixtures) (e.g.
+/// `tampered_vaa`) for one-line rejection tests:
+///
+/// ```ignore
+/// let fixture = negative_fixtures::tampered_vaa(&vaa, &guardians);
+/// assert_verify_rejects(&mut svm, &payer, &fixture.vaa_bytes, &fixture.signatures, |sigs, body| {
+///     build_verify_vaa_instruction(&payer.pubkey(), &wormhole.guardian_set, sigs, wormhole.guardian_set_bump, body)
+/// });
+/// ```
+///
+/// # Panics
+///
+/// Panics if `vaa_bytes` is malformed, if posting or closing the signatures
+/// fails, or if the verify transaction succeeds.
+pub fn assert_verify_rejects<F>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    vaa_bytes: &[u8],
+    signatures: &[[u8; 66]],
+    build_verify_ix: F,
+) -> litesvm::types::FailedTransactionMetadata
+where
+    F: FnOnce(&Pubkey, &[u8]) -> Instruction,
+{
+    let (guardian_set_index, _, vaa_body) =
+        crate::verify::parse_signed_vaa(vaa_bytes).expect("assert_verify_rejects: bad VAA");
+
+    let posted = post_signatures(svm, payer, guardian_set_index, signatures)
+        .expect("assert_verify_rejects: post_signatures failed");
+
+    let ix = build_verify_ix(&posted.pubkey, vaa_body);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let result = svm.send_transaction(tx);
+
+    close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())
+        .expect("assert_verify_rejects: close_signatures failed");
+
+    match result {
+        Ok(meta) => panic!(
+            "verification succeeded but was expected to be rejected\nlogs: {:#?}",
+            meta.logs
+        ),
+        Err(failed) => failed,
+    }
+}
+
+/// Assert that the verify instruction rejects a signatures account that was
+/// never posted.
+///
+/// Builds the instruction with `build_verify_ix(bogus_signatures, vaa_body)`
+/// and sends it. `bogus_signatures` can be an address with no account at all,
+/// or an account holding arbitrary data (e.g. from
+/// [`create_rent_exempt_account`]). Either way the Verify VAA Shim does not own
+/// it, so a program that really verifies through the shim fails the CPI and
+/// the transaction. A success means the program accepts unverified VAAs.
+/// Returns the failed transaction so the caller can inspect the error or logs.
+///
+/// # Panics
+///
+/// Panics if the verify transaction succeeds.
+pub fn assert_requires_posted_signatures<F>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    vaa_body: &[u8],
+    bogus_signatures: &Pubkey,
+    build_verify_ix: F,
+) -> litesvm::types::FailedTransactionMetadata
+where
+    F: FnOnce(&Pubkey, &[u8]) -> Instruction,
+{
+    let ix = build_verify_ix(bogus_signatures, vaa_body);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    match svm.send_transaction(tx) {
+        Ok(meta) => panic!(
+            "verification succeeded with unposted signatures account {}\nlogs: {:#?}",
+            bogus_signatures, meta.logs
+        ),
+        Err(failed) => failed,
+    }
+}
+
+/// Assert that verifying `vaa_bytes` consumes fewer than `max_cu` compute units.
+///
+/// Posts `signatures` under the guardian set index in the VAA header, builds
+/// the verify instruction with `build_verify_ix(signatures_pubkey, vaa_body)`,
+/// simulates it, and closes the signatures account again. Returns the compute
+/// units consumed, so CI can also track the figure over time.
+///
+/// # Panics
+///
+/// Panics if `vaa_bytes` is malformed, if posting or closing the signatures
+/// fails, if the simulation fails, or if it consumes `max_cu` or more compute
+/// units (reporting the actual figure).
+pub fn assert_verify_cu_under<F>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    vaa_bytes: &[u8],
+    signatures: &[[u8; 66]],
+    max_cu: u64,
+    build_verify_ix: F,
+) -> u64
+where
+    F: FnOnce(&Pubkey, &[u8]) -> Instruction,
+{
+    let (guardian_set_index, _, vaa_body) =
+        crate::verify::parse_signed_vaa(vaa_bytes).expect("assert_verify_cu_under: bad VAA");
+
+    let posted = post_signatures(svm, payer, guardian_set_index, signatures)
+        .expect("assert_verify_cu_under: post_signatures failed");
+
+    let ix = build_verify_ix(&posted.pubkey, vaa_body);
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let result = svm.simulate_transaction(tx);
+
+    close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())
+        .expect("assert_verify_cu_under: close_signatures failed");
+
+    let consumed = match result {
+        Ok(info) => info.meta.compute_units_consumed,
+        Err(failed) => panic!(
+            "verification failed: {:?}\nlogs: {:#?}",
+            failed.err, failed.meta.logs
+        ),
+    };
+    assert!(
+        consumed < max_cu,
+        "verification consumed {} compute units, budget is under {}",
+        consumed,
+        max_cu
+    );
+    consumed
+}
+
+/// Smoke test: verify a VAA carrying `payload` in a fresh LiteSVM.
+///
+/// Spins up LiteSVM with the bundled Wormhole programs and the default guardian
+/// ([`TestGuardian::default`](crate::TestGuardian::default)) at index 0, posts
+/// the signatures, and calls the Verify VAA Shim's `verify_hash` directly.
+/// Returns whether every step succeeded; use it as a first-run check that the
+/// environment works, not as a test of your own program.
+#[cfg(feature = "bundled-fixtures")]
+pub fn smoke_verify(payload: &[u8]) -> bool {
+    let guardians = TestGuardianSet::single(crate::TestGuardian::default());
+    let vaa = crate::TestVaa::new(1, [0xAB; 32], 0, payload.to_vec());
+
+    shim_verify_hash(
+        &guardians,
+        0,
+        &vaa.guardian_signatures(&guardians),
+        vaa.digest(),
+    )
+}
+
+/// Property: the offline verifier and the Verify VAA Shim agree on `vaa_bytes`.
+///
+/// Runs [`verify_signatures`](crate::verify_signatures) over the VAA's
+/// signatures and body digest, then installs `guardians` at the header's
+/// guardian set index in a fresh LiteSVM, posts the signatures and calls the
+/// shim's `verify_hash`. A failed post counts as an on-chain rejection.
+/// Returns the shared verdict, or `None` if `vaa_bytes` does not parse (so
+/// there is nothing to compare).
+///
+/// It panics rather than returning an error, so it drops straight into a
+/// `proptest` or `quickcheck` body over generated VAAs without this crate
+/// depending on either.
+///
+/// # Panics
+///
+/// Panics with both results if the offline verifier and the shim disagree.
+#[cfg(feature = "bundled-fixtures")]
+pub fn prop_verify_consistency(vaa_bytes: &[u8], guardians: &TestGuardianSet) -> Option<bool> {
+    let (guardian_set_index, signatures, body) = crate::verify::parse_signed_vaa(vaa_bytes).ok()?;
+    let digest = crate::vaa_body_digest(body);
+
+    let offline = crate::verify_signatures(&digest, &signatures, &guardians.eth_addresses());
+    // Install the set at the header's index even if it is associated with another.
+    let unindexed = TestGuardianSet::new(guardians.iter().cloned().collect());
+    let on_chain = shim_verify_hash(&unindexed, guardian_set_index, &signatures, digest);
+
+    assert_eq!(
+        offline.is_ok(),
+        on_chain,
+        "offline verifier returned {:?} but the Verify VAA Shim {}",
+        offline,
+        if on_chain { "accepted" } else { "rejected" }
+    );
+    Some(on_chain)
+}
+
+/// Install `guardians` in a fresh LiteSVM, post `signatures` and call the
+/// Verify VAA Shim's `verify_hash` on `digest`. Returns whether every step
+/// succeeded.
+#[cfg(feature = "bundled-fixtures")]
+fn shim_verify_hash(
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+    digest: [u8; 32],
+) -> bool {
+    use wormhole_svm_shim::verify_vaa::{VerifyHash, VerifyHashAccounts, VerifyHashData};
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    if svm.airdrop(&payer.pubkey(), 1_000_000_000).is_err() {
+        return false;
+    }
+
+    let Ok(wormhole) = setup_wormhole(
+        &mut svm,
+        guardians,
+        guardian_set_index,
+        WormholeProgramsConfig::default(),
+    ) else {
+        return false;
+    };
+
+    let Ok(posted) = post_signatures(&mut svm, &payer, guardian_set_index, signatures) else {
+        return false;
+    };
+
+    let ix = VerifyHash {
+        program_id: &VERIFY_VAA_SHIM_PROGRAM_ID,
+        accounts: VerifyHashAccounts {
+            guardian_set: &wormhole.guardian_set,
+            guardian_signatures: &posted.pubkey,
+        },
+        data: VerifyHashData::new(wormhole.guardian_set_bump, digest),
+    }
+    .instruction();
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx).is_ok()
+}
+
+/// Outcome of one transaction in a [`FlowTrace`].
+#[derive(Clone, Debug, Default)]
+pub struct TxTrace {
+    /// Program logs.
+    pub logs: Vec<String>,
+    /// Compute units consumed.
+    pub compute_units_consumed: u64,
+    /// The transaction error, if it failed.
+    pub error: Option<String>,
+}
+
+impl TxTrace {
+    fn from_result(result: litesvm::types::TransactionResult) -> Self {
+        match result {
+            Ok(meta) => Self {
+                logs: meta.logs,
+                compute_units_consumed: meta.compute_units_consumed,
+                error: None,
+            },
+            Err(failed) => Self {
+                logs: failed.meta.logs,
+                compute_units_consumed: failed.meta.compute_units_consumed,
+                error: Some(format!("{:?}", failed.err)),
+            },
+        }
+    }
+
+    /// Whether the transaction succeeded.
+    pub fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Everything [`verify_flow_trace`] observed, for diagnosing a failing flow.
+#[derive(Clone, Debug)]
+pub struct FlowTrace {
+    /// The signed VAA.
+    pub vaa_bytes: Vec<u8>,
+    /// The VAA body digest the guardians signed.
+    pub digest: [u8; 32],
+    /// The guardian signatures account the flow posted to.
+    pub signatures_account: Pubkey,
+    /// The `post_signatures` transaction.
+    pub post: TxTrace,
+    /// The verify transaction, or `None` if posting failed.
+    pub verify: Option<TxTrace>,
+    /// The `close_signatures` transaction, or `None` if posting failed.
+    pub close: Option<TxTrace>,
+    /// Lamports returned to the payer by closing the signatures account.
+    pub reclaimed_lamports: u64,
+}
+
+/// Run post → verify → close for `vaa` and record every intermediate result.
+///
+/// Signs `vaa` with `guardians`, posts the signatures under the VAA's guardian
+/// set index, sends `build_verify_ix(signatures_pubkey, vaa_body)`, and closes
+/// the signatures account back to `payer`, capturing each transaction's logs
+/// and compute units. Failures are recorded rather than returned, and the
+/// flow carries on to close the account after a failed verify.
+///
+/// This is an opt-in debugging entry point: it sends the same transactions as
+/// [`with_posted_signatures`] but keeps everything a failure report needs.
+pub fn verify_flow_trace<F>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    vaa: &crate::TestVaa,
+    guardians: &TestGuardianSet,
+    build_verify_ix: F,
+) -> FlowTrace
+where
+    F: FnOnce(&Pubkey, &[u8]) -> Instruction,
+{
+    let signed = vaa.sign_full(guardians);
+    let signatures_keypair = Keypair::new();
+    let signatures_account = signatures_keypair.pubkey();
+
+    let post_ix = build_post_signatures_ix(
+        &payer.pubkey(),
+        &signatures_account,
+        vaa.guardian_set_index,
+        &signed.guardian_signatures,
+    );
+    let post_tx = Transaction::new_signed_with_payer(
+        &[post_ix],
+        Some(&payer.pubkey()),
+        &[payer, &signatures_keypair],
+        svm.latest_blockhash(),
+    );
+    let post = TxTrace::from_result(svm.send_transaction(post_tx));
+
+    let mut trace = FlowTrace {
+        vaa_bytes: signed.vaa_bytes,
+        digest: vaa.digest(),
+        signatures_account,
+        post,
+        verify: None,
+        close: None,
+        reclaimed_lamports: 0,
+    };
+    if !trace.post.succeeded() {
+        return trace;
+    }
+
+    let verify_ix = build_verify_ix(&signatures_account, &vaa.body());
+    let verify_tx = Transaction::new_signed_with_payer(
+        &[verify_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    trace.verify = Some(TxTrace::from_result(svm.send_transaction(verify_tx)));
+
+    let rent = svm
+        .get_account(&signatures_account)
+        .map_or(0, |account| account.lamports);
+    let close_tx = Transaction::new_signed_with_payer(
+        &[build_close_signatures_ix(
+            &signatures_account,
+            &payer.pubkey(),
+        )],
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let close = TxTrace::from_result(svm.send_transaction(close_tx));
+    if close.succeeded() {
+        trace.reclaimed_lamports = rent;
+    }
+    trace.close = Some(close);
+
+    trace
+}
+
+/// Execute a closure that verifies a VAA, WITHOUT automatic verification check.
+///
+/// This is the unchecked version of [`with_vaa`] that skips the automatic negative
+/// test. Use this only when you have a specific reason to skip the safety check,
+/// such as testing error handling paths or when you need full control over execution.
+///
+/// **Prefer [`with_vaa`] in most cases** - it automatically ensures your program
+/// actually verifies VAAs.
+///
+/// # Example
+///
+/// ```ignore
+/// // Only use this if you have a specific reason to skip the safety check
+/// with_vaa_unchecked(&mut svm, &payer, &guardians, 0, &vaa, |svm, sigs_pubkey, vaa_body| {
+///     let tx = Transaction::new_signed_with_payer(...);
+///     svm.send_transaction(tx).map_err(|e| format!("{:?}", e))
+/// })?;
+/// ```
+pub fn with_vaa_unchecked<F, T, E>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guardians: &TestGuardianSet,
+    guardian_set_index: u32,
+    vaa: &crate::TestVaa,
+    f: F,
+) -> Result<T, WormholeTestError>
+where
+    F: FnOnce(&mut LiteSVM, &Pubkey, &[u8]) -> Result<T, E>,
+    E: std::fmt::Display,
+{
+    let vaa_body = vaa.body();
+    let signatures = vaa.guardian_signatures(guardians);
+
+    let posted = post_signatures(svm, payer, guardian_set_index, &signatures)?;
+
+    let result = f(svm, &posted.pubkey, &vaa_body)
+        .map_err(|e| WormholeTestError::TransactionFailed(format!("closure: {}", e)))?;
+
+    close_signatures(svm, payer, &posted.pubkey, &payer.pubkey())?;
+
+    Ok(result)
+}
+
+/// Post several signature batches and build one transaction that verifies them all.
+///
+/// Each batch is posted to its own signatures account, then `build_verify_ix`
+/// is called with each account pubkey and the batch position to produce the
+/// consumer's verify instruction. The instructions are combined into a single
+/// transaction signed by `payer`, which is returned unsent together with the
+/// posted accounts (close them with [`close_signatures`] when done).
+///
+/// The transaction size is checked against the packet limit *before* anything
+/// is posted, so a [`WormholeTestError::TransactionTooLarge`] error leaves the
+/// SVM untouched.
+///
+/// # Example
+///
+/// ```ignore
+/// let (tx, posted) = build_multi_verify_transaction(
+///     &mut svm,
+///     &payer,
+///     0,
+///     &[vaa_a.guardian_signatures(&guardians), vaa_b.guardian_signatures(&guardians)],
+///     |sigs_pubkey, i| build_my_verify_ix(sigs_pubkey, &bodies[i]),
+/// )?;
+/// svm.send_transaction(tx)?;
+/// ```
+pub fn build_multi_verify_transaction<F>(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    guardian_set_index: u32,
+    batches: &[Vec<[u8; 66]>],
+    build_verify_ix: F,
+) -> Result<(Transaction, Vec<PostedSignatures>), WormholeTestError>
+where
+    F: Fn(&Pubkey, usize) -> Instruction,
+{
+    // Size only depends on the number of distinct accounts, so placeholder
+    // pubkeys give the same result as the real signatures accounts.
+    let placeholder_ixs: Vec<Instruction> = (0..batches.len())
+        .map(|i| build_verify_ix(&Pubkey::new_unique(), i))
+        .collect();
+    let placeholder_tx = Transaction::new_signed_with_payer(
+        &placeholder_ixs,
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+    let size = transaction_size(&placeholder_tx);
+    if size > PACKET_DATA_SIZE {
+        return Err(WormholeTestError::TransactionTooLarge {
+            size,
+            max: PACKET_DATA_SIZE,
+        });
+    }
+
+    let posted = post_signatures_batch(svm, payer, guardian_set_index, batches)?;
+
+    let instructions: Vec<Instruction> = posted
+        .iter()
+        .enumerate()
+        .map(|(i, p)| build_verify_ix(&p.pubkey, i))
+        .collect();
+    let tx = Transaction::new_signed_with_payer(
+        &instructions,
+        Some(&payer.pubkey()),
+        &[payer],
+        svm.latest_blockhash(),
+    );
+
+    Ok((tx, posted))
+}
+
+/// Build, but don't send, the transaction that verifies `vaa`.
+///
+/// Assumes the guardian signatures are already posted at `guardian_signatures`.
+/// The instruction comes from `build_verify_ix(guardian_signatures, vaa_body)`
+/// and the transaction is signed by `payer` with `recent_blockhash`. With a
+/// fixed payer keypair and blockhash (e.g. `Hash::default()`), the result is
+/// byte-for-byte stable, so it can be serialized and compared against a golden
+/// snapshot to catch any change in instruction layout.
+pub fn build_verify_transaction<F>(
+    payer: &Keypair,
+    guardian_signatures: &Pubkey,
+    vaa: &crate::TestVaa,
+    recent_blockhash: Hash,
+    build_verify_ix: F,
+) -> Transaction
+where
+    F: FnOnce(&Pubkey, &[u8]) -> Instruction,
+{
+    let ix = build_verify_ix(guardian_signatures, &vaa.body());
+    Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], recent_blockhash)
+}
+
+/// Serialized size of a legacy transaction in bytes (signatures + message).
+pub fn transaction_size(tx: &Transaction) -> usize {
+    let signature_count = tx.signatures.len();
+    // The signature count is a compact-u16 (1 byte below 128, 2 bytes below 16384).
+    let count_len = if signature_count < 0x80 { 1 } else { 2 };
+    count_len + signature_count * 64 + tx.message_data().len()
+}
+
+/// Build a v0 transaction that loads accounts through address lookup tables.
+///
+/// Resolved instruction groups often reference more accounts than a legacy
+/// message can hold; any account found in `lookup_tables` is referenced by
+/// index instead of by key. The transaction is signed by `payer` only.
+pub fn build_v0_transaction(
+    payer: &Keypair,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Result<VersionedTransaction, WormholeTestError> {
+    let message = v0::Message::try_compile(
+        &payer.pubkey(),
+        instructions,
+        lookup_tables,
+        recent_blockhash,
+    )
+    .map_err(|e| WormholeTestError::TransactionBuild(format!("compile v0 message: {}", e)))?;
+
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer])
+        .map_err(|e| WormholeTestError::TransactionBuild(format!("sign v0 transaction: {}", e)))
+}
+
+/// Create an address lookup table holding `addresses` in LiteSVM.
+///
+/// The account is written directly rather than through the lookup table
+/// program, with no authority and every address active from the current slot,
+/// so it can be passed to [`build_v0_transaction`] immediately.
+pub fn create_lookup_table(
+    svm: &mut LiteSVM,
+    addresses: &[Pubkey],
+) -> Result<AddressLookupTableAccount, WormholeTestError> {
+    let table = AddressLookupTable {
+        meta: LookupTableMeta {
+            last_extended_slot: svm.get_sysvar::<solana_sdk::clock::Clock>().slot,
+            last_extended_slot_start_index: u8::try_from(addresses.len()).map_err(|_| {
+                WormholeTestError::TransactionBuild(format!(
+                    "Lookup table holds at most 255 addresses, got {}",
+                    addresses.len()
+                ))
+            })?,
+            ..LookupTableMeta::default()
+        },
+        addresses: Cow::Borrowed(addresses),
+    };
+    let data = table.serialize_for_tests().map_err(|e| {
+        WormholeTestError::TransactionBuild(format!("serialize lookup table: {}", e))
+    })?;
+
+    let key = Pubkey::new_unique();
+    let account = Account {
+        lamports: rent_exempt_lamports(svm, data.len()),
+        data,
+        owner: address_lookup_table::program::id(),
+        executable: false,
+        rent_epoch: 0,
+    };
+    svm.set_account(key, account).unwrap();
+
+    Ok(AddressLookupTableAccount {
+        key,
+        addresses: addresses.to_vec(),
+    })
+}
+
+/// Serialized size of the transaction [`post_signatures`] sends for `signature_count` signatures.
+///
+/// The transaction is built exactly as `post_signatures` builds it (payer and
+/// signatures-account signers, one `PostSignatures` instruction), so the result
+/// can be compared against [`PACKET_DATA_SIZE`] to decide when a guardian set
+/// is too large to post in a single transaction.
+pub fn post_signatures_tx_size(signature_count: usize) -> usize {
+    let payer = Keypair::new();
+    let signatures_keypair = Keypair::new();
+    let ix = build_post_signatures_ix(
+        &payer.pubkey(),
+        &signatures_keypair.pubkey(),
+        0,
+        &vec![[0u8; 66]; signature_count],
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[&payer, &signatures_keypair],
+        Hash::default(),
+    );
+    transaction_size(&tx)
+}
+
+/// Build a post_signatures instruction without sending it.
+///
+/// Useful if you need to combine this with other instructions in a single transaction.
+pub fn build_post_signatures_ix(
+    payer: &Pubkey,
+    guardian_signatures_keypair: &Pubkey,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+) -> Instruction {
+    wormhole_svm_submit::build_post_signatures_ix(
+        payer,
+        guardian_signatures_keypair,
+        &VERIFY_VAA_SHIM_PROGRAM_ID,
+        guardian_set_index,
+        signatures,
+    )
+}
+
+/// Size in bytes of the Verify VAA Shim guardian signatures account holding
+/// `signature_count` signatures.
+///
+/// Layout: `discriminator (8) | refund_recipient (32) | guardian_set_index (4)
+/// | signatures (borsh Vec: 4-byte length + 66 each)`.
+pub fn guardian_signatures_account_size(signature_count: usize) -> usize {
+    8 + 32 + 4 + 4 + 66 * signature_count
+}
+
+/// Serialize a Verify VAA Shim guardian signatures account.
+///
+/// Produces exactly the data [`post_signatures`] leaves behind, laid out as
+/// described in [`guardian_signatures_account_size`]: the
+/// `GUARDIAN_SIGNATURES_DISCRIMINATOR` from `wormhole-svm-definitions`, the
+/// refund recipient, the guardian set index (big-endian, as the shim stores
+/// it) and the signatures. Write it with `svm.set_account` (owned by
+/// [`VERIFY_VAA_SHIM_PROGRAM_ID`]) to skip the transaction, or edit it first to
+/// craft a malformed account.
+pub fn guardian_signatures_account_data(
+    guardian_set_index: u32,
+    refund_recipient: &Pubkey,
+    signatures: &[[u8; 66]],
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(guardian_signatures_account_size(signatures.len()));
+    data.extend_from_slice(&wormhole_svm_definitions::GUARDIAN_SIGNATURES_DISCRIMINATOR);
+    data.extend_from_slice(refund_recipient.as_ref());
+    data.extend_from_slice(&guardian_set_index.to_be_bytes());
+    data.extend_from_slice(&(signatures.len() as u32).to_le_bytes());
+    for sig in signatures {
+        data.extend_from_slice(sig);
+    }
+    data
+}
+
+/// Build a post_signatures instruction and report the size of the account it creates.
+///
+/// Returns the instruction and the guardian signatures account size, so the
+/// caller can compute the rent `payer` must cover (e.g. with
+/// `Rent::minimum_balance`) before combining the instruction with others in a
+/// single transaction. The `guardian_signatures` keypair must sign that
+/// transaction.
+pub fn build_post_signatures_instruction(
+    payer: &Pubkey,
+    guardian_signatures: &Pubkey,
+    guardian_set_index: u32,
+    signatures: &[[u8; 66]],
+) -> (Instruction, usize) {
+    (
+        build_post_signatures_ix(payer, guardian_signatures, guardian_set_index, signatures),
+        guardian_signatures_account_size(signatures.len()),
+    )
+}
+
+/// Build a close_signatures instruction without sending it.
+///
+/// Useful if you need to combine this with other instructions in a single transaction.
+pub fn build_close_signatures_ix(
+    guardian_signatures: &Pubkey,
     refund_recipient: &Pubkey,
 ) -> Instruction {
     wormhole_svm_submit::build_close_signatures_ix(
@@ -767,6 +2037,242 @@ pub fn build_close_signatures_ix(
     )
 }
 
+// =============================================================================
+// Guardian set upgrade through the Core Bridge
+// =============================================================================
+
+/// Core Bridge instruction discriminators (borsh enum variant indices).
+const CORE_BRIDGE_POST_VAA: u8 = 2;
+const CORE_BRIDGE_UPGRADE_GUARDIAN_SET: u8 = 6;
+const CORE_BRIDGE_VERIFY_SIGNATURES: u8 = 7;
+
+/// Number of guardian slots in the Core Bridge `verify_signatures` signer map.
+const CORE_BRIDGE_MAX_SIGNERS: usize = 19;
+
+/// Rotate to a new guardian set through the Core Bridge's own instructions.
+///
+/// Unlike [`setup_wormhole`], which writes guardian set accounts directly,
+/// this drives the real rotation path:
+/// 1. Builds a guardian set upgrade governance VAA signed by `guardians`
+///    (the set currently installed at `wormhole.guardian_set`)
+/// 2. Verifies the signatures (`verify_signatures`, one secp256k1 instruction
+///    per guardian) and posts the VAA (`post_vaa`)
+/// 3. Executes `upgrade_guardian_set` and checks the new set was installed
+///
+/// The governance VAA uses `new_index` as its sequence, so each upgrade has a
+/// distinct claim account. Returns the accounts for the new guardian set.
+pub fn upgrade_guardian_set(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    wormhole: &WormholeAccounts,
+    guardians: &TestGuardianSet,
+    new_set: &TestGuardianSet,
+    new_index: u32,
+) -> Result<WormholeAccounts, WormholeTestError> {
+    use solana_sdk::{instruction::AccountMeta, system_program, sysvar};
+
+    let current_index = svm
+        .get_account(&wormhole.guardian_set)
+        .ok_or(WormholeTestError::AccountNotFound(wormhole.guardian_set))?
+        .data
+        .get(0..4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+        .ok_or_else(|| WormholeTestError::AccountDeserialize {
+            pubkey: wormhole.guardian_set,
+            reason: "guardian set account is shorter than its index".to_string(),
+        })?;
+
+    let vaa = crate::TestVaa {
+        guardian_set_index: current_index,
+        ..crate::TestVaa::new(
+            crate::payloads::GOVERNANCE_CHAIN,
+            crate::payloads::GOVERNANCE_EMITTER,
+            new_index as u64,
+            crate::payloads::guardian_set_upgrade(new_index, &new_set.eth_addresses()),
+        )
+    };
+    let body_hash = crate::vaa_body_hash(&vaa.body());
+
+    // The signer map has one slot per key in the guardian set account, and a
+    // guardian's slot is its index (see `TestGuardianSet::eth_addresses`).
+    // Reject any index the map cannot hold before sending anything.
+    let signatures = vaa.guardian_signatures(guardians);
+    if let Some(sig) = signatures
+        .iter()
+        .find(|sig| sig[0] as usize >= CORE_BRIDGE_MAX_SIGNERS)
+    {
+        return Err(WormholeTestError::SignerIndexOutOfRange {
+            index: sig[0],
+            max: CORE_BRIDGE_MAX_SIGNERS,
+        });
+    }
+
+    // 1. Verify signatures, one guardian per transaction.
+    let signature_set = Keypair::new();
+    let addresses = guardians.eth_addresses();
+    for sig in &signatures {
+        let slot = sig[0] as usize;
+        let mut signers = [-1i8; CORE_BRIDGE_MAX_SIGNERS];
+        signers[slot] = 0;
+
+        let mut data = vec![CORE_BRIDGE_VERIFY_SIGNATURES];
+        data.extend(signers.iter().map(|&s| s as u8));
+
+        let verify_ix = Instruction {
+            program_id: CORE_BRIDGE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(payer.pubkey(), true),
+                AccountMeta::new_readonly(wormhole.guardian_set, false),
+                AccountMeta::new(signature_set.pubkey(), true),
+                AccountMeta::new_readonly(sysvar::instructions::ID, false),
+                AccountMeta::new_readonly(sysvar::rent::ID, false),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data,
+        };
+        let secp_ix =
+            build_secp256k1_ix(&body_hash, sig[1..].try_into().unwrap(), &addresses[slot]);
+
+        send_core_bridge_tx(
+            svm,
+            payer,
+            &[secp_ix, verify_ix],
+            &[&signature_set],
+            "verify_signatures",
+        )?;
+    }
+
+    // 2. Post the VAA.
+    let (posted_vaa, _) =
+        Pubkey::find_program_address(&[b"PostedVAA", &body_hash], &CORE_BRIDGE_PROGRAM_ID);
+
+    let mut data = vec![CORE_BRIDGE_POST_VAA, 1];
+    data.extend_from_slice(&vaa.guardian_set_index.to_le_bytes());
+    data.extend_from_slice(&vaa.timestamp.to_le_bytes());
+    data.extend_from_slice(&vaa.nonce.to_le_bytes());
+    data.extend_from_slice(&vaa.emitter_chain.to_le_bytes());
+    data.extend_from_slice(&vaa.emitter_address);
+    data.extend_from_slice(&vaa.sequence.to_le_bytes());
+    data.push(vaa.consistency_level);
+    data.extend(crate::instruction_data::length_prefixed(&vaa.payload));
+
+    let post_vaa_ix = Instruction {
+        program_id: CORE_BRIDGE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(wormhole.guardian_set, false),
+            AccountMeta::new_readonly(CORE_BRIDGE_CONFIG, false),
+            AccountMeta::new_readonly(signature_set.pubkey(), false),
+            AccountMeta::new(posted_vaa, false),
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new_readonly(sysvar::clock::ID, false),
+            AccountMeta::new_readonly(sysvar::rent::ID, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data,
+    };
+    send_core_bridge_tx(svm, payer, &[post_vaa_ix], &[], "post_vaa")?;
+
+    // 3. Upgrade the guardian set.
+    let (claim, _) = Pubkey::find_program_address(
+        &[
+            &vaa.emitter_address,
+            &vaa.emitter_chain.to_be_bytes(),
+            &vaa.sequence.to_be_bytes(),
+        ],
+        &CORE_BRIDGE_PROGRAM_ID,
+    );
+    let (guardian_set, guardian_set_bump) = derive_guardian_set(new_index);
+
+    let upgrade_ix = Instruction {
+        program_id: CORE_BRIDGE_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new(payer.pubkey(), true),
+            AccountMeta::new(CORE_BRIDGE_CONFIG, false),
+            AccountMeta::new_readonly(posted_vaa, false),
+            AccountMeta::new(claim, false),
+            AccountMeta::new(wormhole.guardian_set, false),
+            AccountMeta::new(guardian_set, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: vec![CORE_BRIDGE_UPGRADE_GUARDIAN_SET],
+    };
+    send_core_bridge_tx(svm, payer, &[upgrade_ix], &[], "upgrade_guardian_set")?;
+
+    // Confirm the Core Bridge wrote the expected index and keys (it sets the
+    // creation and expiration times itself).
+    let expected = build_guardian_set_data(new_set, new_index);
+    let keys_end = expected.len() - 8;
+    let installed = svm
+        .get_account(&guardian_set)
+        .map(|account| account.data)
+        .unwrap_or_default();
+    if installed.get(..keys_end) != Some(&expected[..keys_end]) {
+        return Err(WormholeTestError::TransactionFailed(format!(
+            "upgrade_guardian_set did not install guardian set {} at {}",
+            new_index, guardian_set
+        )));
+    }
+
+    Ok(WormholeAccounts {
+        guardian_set,
+        guardian_set_bump,
+    })
+}
+
+/// Build a secp256k1 precompile instruction carrying one signature over
+/// `message`, with all offsets pointing into the instruction at index 0.
+fn build_secp256k1_ix(
+    message: &[u8; 32],
+    signature: [u8; 65],
+    eth_address: &[u8; 20],
+) -> Instruction {
+    const DATA_START: u16 = 1 + 11;
+    const SIGNATURE_OFFSET: u16 = DATA_START + 20;
+    const MESSAGE_OFFSET: u16 = SIGNATURE_OFFSET + 65;
+
+    let mut data = vec![1u8];
+    data.extend_from_slice(&SIGNATURE_OFFSET.to_le_bytes());
+    data.push(0);
+    data.extend_from_slice(&DATA_START.to_le_bytes());
+    data.push(0);
+    data.extend_from_slice(&MESSAGE_OFFSET.to_le_bytes());
+    data.extend_from_slice(&(message.len() as u16).to_le_bytes());
+    data.push(0);
+    data.extend_from_slice(eth_address);
+    data.extend_from_slice(&signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: solana_sdk::secp256k1_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+fn send_core_bridge_tx(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    instructions: &[Instruction],
+    extra_signers: &[&Keypair],
+    name: &str,
+) -> Result<(), WormholeTestError> {
+    let mut signers = vec![payer];
+    signers.extend_from_slice(extra_signers);
+
+    let blockhash = svm.latest_blockhash();
+    let tx = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &signers,
+        blockhash,
+    );
+
+    svm.send_transaction(tx)
+        .map_err(|e| WormholeTestError::TransactionFailed(format!("{}: {:?}", name, e)))?;
+
+    Ok(())
+}
+
 // =============================================================================
 // Posted Message Capture and VAA Construction
 // =============================================================================
@@ -887,6 +2393,7 @@ impl PostedMessageInfo {
             timestamp: self.timestamp,
             nonce: self.nonce,
             consistency_level: self.consistency_level,
+            version: crate::VAA_VERSION,
             guardian_set_index: 0,
             checks: Default::default(),
         }
@@ -968,12 +2475,320 @@ pub fn read_emitter_sequence(svm: &LiteSVM, emitter: &Pubkey) -> Option<u64> {
     }
 }
 
+/// Read the guardian addresses installed at `guardian_set_index`.
+///
+/// Returns `None` if the guardian set account doesn't exist or is malformed.
+pub fn read_guardian_set_addresses(
+    svm: &LiteSVM,
+    guardian_set_index: u32,
+) -> Option<Vec<[u8; 20]>> {
+    let (address, _) = derive_guardian_set(guardian_set_index);
+    let account = svm.get_account(&address)?;
+
+    crate::verify::parse_guardian_set_account(&account.data)
+        .ok()
+        .map(|(_, keys)| keys)
+}
+
+/// Assert that the guardian set at `guardian_set_index` holds exactly `expected`, in order.
+///
+/// # Panics
+///
+/// Panics if the set is missing, has a different number of guardians, or
+/// differs at some position (reporting the first differing index and both
+/// addresses).
+pub fn assert_guardian_addresses(svm: &LiteSVM, guardian_set_index: u32, expected: &[[u8; 20]]) {
+    let actual = read_guardian_set_addresses(svm, guardian_set_index)
+        .unwrap_or_else(|| panic!("guardian set {} is not installed", guardian_set_index));
+
+    if let Some(i) = actual.iter().zip(expected).position(|(a, e)| a != e) {
+        panic!(
+            "guardian set {} differs at index {}: installed 0x{}, expected 0x{}",
+            guardian_set_index,
+            i,
+            hex::encode(actual[i]),
+            hex::encode(expected[i])
+        );
+    }
+    assert_eq!(
+        actual.len(),
+        expected.len(),
+        "guardian set {} has {} guardian(s), expected {}",
+        guardian_set_index,
+        actual.len(),
+        expected.len()
+    );
+}
+
+/// Assert that `pubkey` was fully closed: no lamports and no data left behind.
+///
+/// A missing account passes, as does an empty zero-lamport one (LiteSVM may
+/// keep those around until the next transaction). Use it after
+/// [`close_signatures`] to confirm the account was deallocated rather than
+/// just drained.
+///
+/// # Panics
+///
+/// Panics with the residual lamports and data length if the account survived.
+pub fn assert_account_closed(svm: &LiteSVM, pubkey: &Pubkey) {
+    if let Some(account) = svm.get_account(pubkey) {
+        assert!(
+            account.lamports == 0 && account.data.is_empty(),
+            "account {} was not closed: {} lamport(s) and {} byte(s) of data remain",
+            pubkey,
+            account.lamports,
+            account.data.len()
+        );
+    }
+}
+
+/// Assert that the Verify VAA Shim is loaded at [`VERIFY_VAA_SHIM_PROGRAM_ID`].
+///
+/// A setup sanity check: a missing program otherwise surfaces as a confusing
+/// transaction error much later.
+///
+/// # Panics
+///
+/// Panics if the program account is missing or not executable.
+pub fn assert_shim_loaded(svm: &LiteSVM) {
+    assert_program_loaded(svm, "Verify VAA Shim", &VERIFY_VAA_SHIM_PROGRAM_ID);
+}
+
+/// Assert that the Core Bridge is loaded at [`CORE_BRIDGE_PROGRAM_ID`].
+///
+/// # Panics
+///
+/// Panics if the program account is missing or not executable.
+pub fn assert_core_bridge_loaded(svm: &LiteSVM) {
+    assert_program_loaded(svm, "Core Bridge", &CORE_BRIDGE_PROGRAM_ID);
+}
+
+fn assert_program_loaded(svm: &LiteSVM, name: &str, program_id: &Pubkey) {
+    let account = svm.get_account(program_id).unwrap_or_else(|| {
+        panic!(
+            "{} is not loaded at {} (call load_wormhole_programs or setup_wormhole first)",
+            name, program_id
+        )
+    });
+    assert!(
+        account.executable,
+        "{} account at {} exists but is not executable",
+        name, program_id
+    );
+}
+
+/// Rent-exempt minimum balance for an account with `data_len` bytes of data,
+/// per the SVM's rent sysvar.
+pub fn rent_exempt_lamports(svm: &LiteSVM, data_len: usize) -> u64 {
+    svm.get_sysvar::<Rent>().minimum_balance(data_len)
+}
+
+/// Create a system-owned account with `data_len` bytes of zeroed data, funded
+/// by `payer` to exactly [`rent_exempt_lamports`].
+///
+/// Returns the new account's keypair. Funding to the minimum (rather than
+/// airdropping a round number) keeps balance assertions exact.
+pub fn create_rent_exempt_account(
+    svm: &mut LiteSVM,
+    payer: &Keypair,
+    data_len: usize,
+) -> Result<Keypair, WormholeTestError> {
+    let account = Keypair::new();
+    let ix = solana_sdk::system_instruction::create_account(
+        &payer.pubkey(),
+        &account.pubkey(),
+        rent_exempt_lamports(svm, data_len),
+        data_len as u64,
+        &solana_sdk::system_program::ID,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[ix],
+        Some(&payer.pubkey()),
+        &[payer, &account],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx).map_err(|e| {
+        WormholeTestError::TransactionFailed(format!("create_rent_exempt_account: {:?}", e))
+    })?;
+
+    Ok(account)
+}
+
+/// Assert that `pubkey` holds at least the rent-exempt minimum for its data length.
+///
+/// # Panics
+///
+/// Panics if the account does not exist, or with its balance and the
+/// required minimum if it is underfunded.
+pub fn assert_rent_exempt(svm: &LiteSVM, pubkey: &Pubkey) {
+    let account = svm
+        .get_account(pubkey)
+        .unwrap_or_else(|| panic!("account {} does not exist", pubkey));
+    let minimum = rent_exempt_lamports(svm, account.data.len());
+
+    assert!(
+        account.lamports >= minimum,
+        "account {} holds {} lamport(s) but {} are required for {} byte(s) of data",
+        pubkey,
+        account.lamports,
+        minimum,
+        account.data.len()
+    );
+}
+
+/// Read the raw data of an account, e.g. one a program wrote after verifying a VAA.
+///
+/// # Panics
+///
+/// Panics if the account does not exist.
+pub fn read_account_data(svm: &LiteSVM, pubkey: &Pubkey) -> Vec<u8> {
+    svm.get_account(pubkey)
+        .unwrap_or_else(|| panic!("account {} does not exist", pubkey))
+        .data
+}
+
+/// Read an account and borsh-deserialize its data as `T`.
+///
+/// Deserialization starts at the first byte and ignores trailing bytes, so
+/// fixed-size accounts with unused space decode cleanly. For Anchor accounts,
+/// include the 8-byte discriminator as the first field of `T` or slice
+/// [`read_account_data`] yourself.
+#[cfg(feature = "borsh")]
+pub fn read_borsh<T: borsh::BorshDeserialize>(
+    svm: &LiteSVM,
+    pubkey: &Pubkey,
+) -> Result<T, WormholeTestError> {
+    let account = svm
+        .get_account(pubkey)
+        .ok_or(WormholeTestError::AccountNotFound(*pubkey))?;
+
+    T::deserialize(&mut account.data.as_slice()).map_err(|e| {
+        WormholeTestError::AccountDeserialize {
+            pubkey: *pubkey,
+            reason: e.to_string(),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::TestGuardian;
     use std::path::Path;
 
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_smoke_verify() {
+        assert!(smoke_verify(b"hello wormhole"));
+        assert!(smoke_verify(&[]));
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_prop_verify_consistency() {
+        use crate::TestVaa;
+
+        let guardians = TestGuardianSet::generate(4, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, b"prop".to_vec());
+
+        assert_eq!(
+            prop_verify_consistency(&vaa.sign(&guardians), &guardians),
+            Some(true)
+        );
+        assert_eq!(
+            prop_verify_consistency(&vaa.sign_with(&guardians, &[0, 1]), &guardians),
+            Some(false)
+        );
+        assert_eq!(
+            prop_verify_consistency(&vaa.sign(&TestGuardianSet::generate(4, 2)), &guardians),
+            Some(false)
+        );
+        assert_eq!(prop_verify_consistency(&[0xFF; 3], &guardians), None);
+    }
+
+    #[test]
+    fn test_new_svm_with_custom_features() {
+        let mut svm = new_svm(&FeatureConfig::Custom(FeatureSet::all_enabled()));
+        let pubkey = Pubkey::new_unique();
+
+        svm.airdrop(&pubkey, 1_000).unwrap();
+        assert_eq!(svm.get_balance(&pubkey), Some(1_000));
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_setup_wormhole_svm() {
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let config = WormholeProgramsConfig::default()
+            .with_features(FeatureConfig::Custom(FeatureSet::all_enabled()));
+
+        let (svm, accounts) = setup_wormhole_svm(&guardians, 0, config).unwrap();
+
+        assert!(svm.get_account(&accounts.guardian_set).is_some());
+        assert!(svm.get_account(&VERIFY_VAA_SHIM_PROGRAM_ID).is_some());
+    }
+
+    #[test]
+    fn test_v0_transaction_with_lookup_table() {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        let recipient = Pubkey::new_unique();
+        let table = create_lookup_table(&mut svm, &[recipient]).unwrap();
+        let ix = solana_sdk::system_instruction::transfer(&payer.pubkey(), &recipient, 1_000_000);
+
+        let tx = build_v0_transaction(
+            &payer,
+            &[ix],
+            std::slice::from_ref(&table),
+            svm.latest_blockhash(),
+        )
+        .unwrap();
+        assert_eq!(tx.message.address_table_lookups().unwrap().len(), 1);
+
+        svm.send_transaction(tx).unwrap();
+        assert_eq!(svm.get_balance(&recipient), Some(1_000_000));
+    }
+
+    #[test]
+    fn test_assert_guardian_set_derived() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let wormhole = setup_wormhole_accounts_only(
+            &mut svm,
+            &guardians,
+            3,
+            WormholeAccountsConfig::default(),
+        )
+        .unwrap();
+
+        assert_guardian_set_derived(&wormhole, 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "guardian set 4 is at")]
+    fn test_assert_guardian_set_derived_mismatch() {
+        let (guardian_set, guardian_set_bump) = derive_guardian_set(3);
+
+        assert_guardian_set_derived(
+            &WormholeAccounts {
+                guardian_set,
+                guardian_set_bump,
+            },
+            4,
+        );
+    }
+
+    #[test]
+    fn test_derive_post_message_event_authority() {
+        assert_eq!(
+            derive_post_message_event_authority(),
+            Pubkey::find_program_address(&[b"__event_authority"], &POST_MESSAGE_SHIM_PROGRAM_ID)
+        );
+    }
+
     #[test]
     fn test_guardian_set_data_structure() {
         let guardians = TestGuardianSet::single(TestGuardian::default());
@@ -1008,6 +2823,16 @@ mod tests {
         assert_eq!(len, 3);
     }
 
+    #[test]
+    fn test_guardian_set_data_expiration() {
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let data = build_guardian_set_data_with_expiration(&guardians, 0, 1_000);
+
+        // Expiration is the last 4 bytes
+        let expiration = u32::from_le_bytes(data[32..36].try_into().unwrap());
+        assert_eq!(expiration, 1_000);
+    }
+
     #[test]
     fn test_search_paths_includes_env_var() {
         std::env::set_var("WORMHOLE_FIXTURES_DIR", "/custom/path");
@@ -1050,6 +2875,22 @@ mod tests {
         assert!(post_shim_account.is_some(), "Post message shim not loaded");
     }
 
+    #[test]
+    fn test_setup_wormhole_rejects_mismatched_set_index() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default()).with_index(1);
+
+        let result = setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default());
+
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::GuardianSetIndexMismatch {
+                set: 1,
+                requested: 0
+            })
+        ));
+    }
+
     #[cfg(feature = "bundled-fixtures")]
     #[test]
     fn test_bundled_fixtures_are_valid_elf() {
@@ -1085,9 +2926,13 @@ mod tests {
         // Post signatures
         let posted = post_signatures(&mut svm, &payer, 0, &sig_arrays).unwrap();
 
-        // Verify signatures account exists
+        // Verify signatures account exists with the expected size
         let sigs_account = svm.get_account(&posted.pubkey);
         assert!(sigs_account.is_some(), "Signatures account should exist");
+        assert_eq!(
+            sigs_account.unwrap().data.len(),
+            guardian_signatures_account_size(sig_arrays.len())
+        );
 
         // Close signatures
         close_signatures(&mut svm, &payer, &posted.pubkey, &payer.pubkey()).unwrap();
@@ -1100,6 +2945,503 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_build_post_signatures_instruction() {
+        let payer = Pubkey::new_unique();
+        let sigs = Pubkey::new_unique();
+        let signatures = vec![[0u8; 66]; 3];
+
+        let (ix, size) = build_post_signatures_instruction(&payer, &sigs, 0, &signatures);
+
+        assert_eq!(ix.program_id, VERIFY_VAA_SHIM_PROGRAM_ID);
+        assert_eq!(
+            ix.data,
+            build_post_signatures_ix(&payer, &sigs, 0, &signatures).data
+        );
+        assert_eq!(size, 48 + 3 * 66);
+    }
+
+    #[test]
+    fn test_build_verify_transaction_is_deterministic() {
+        use crate::TestVaa;
+
+        let payer = Keypair::new_from_array([7; 32]);
+        let sigs = Pubkey::new_from_array([9; 32]);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let program = Pubkey::new_from_array([1; 32]);
+
+        let build = || {
+            build_verify_transaction(&payer, &sigs, &vaa, Hash::default(), |sigs, body| {
+                Instruction::new_with_bytes(
+                    program,
+                    body,
+                    vec![solana_sdk::instruction::AccountMeta::new_readonly(
+                        *sigs, false,
+                    )],
+                )
+            })
+        };
+
+        let tx = build();
+        assert_eq!(tx.message.instructions[0].data, vaa.body());
+        assert_eq!(tx.message_data(), build().message_data());
+        assert_eq!(tx.signatures, build().signatures);
+    }
+
+    #[test]
+    fn test_post_signatures_checked_rejects_out_of_range() {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        let signer = TestGuardianSet::generate(5, 1);
+        let installed = TestGuardianSet::generate(3, 2);
+
+        // Guardian 4 signed, but the set being validated against only has 3.
+        let signatures = signer.sign_vaa_body_with(b"body", &[0, 4]);
+
+        let result = post_signatures_checked(&mut svm, &payer, 0, &signatures, Some(&installed));
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::InvalidSignatures(
+                crate::VerifyError::IndexOutOfRange {
+                    position: 1,
+                    index: 4,
+                    ..
+                }
+            ))
+        ));
+
+        let result = post_signatures_checked(
+            &mut svm,
+            &payer,
+            0,
+            &signatures,
+            Some(&installed.clone().with_index(1)),
+        );
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::GuardianSetIndexMismatch {
+                set: 1,
+                requested: 0
+            })
+        ));
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_post_signatures_checked_gapped_set() {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+        load_wormhole_programs(&mut svm, WormholeProgramsConfig::default()).unwrap();
+
+        // Indices 0, 1 and 3: the set has four slots, so guardian 3 is in
+        // range even though the set has only three guardians.
+        let guardians = TestGuardianSet::from_indexed(vec![
+            (0, TestGuardian::new([1; 32], 0)),
+            (1, TestGuardian::new([2; 32], 0)),
+            (3, TestGuardian::new([3; 32], 0)),
+        ]);
+        let signatures = guardians.sign_vaa_body_with(b"body", &[0, 1, 3]);
+        assert!(guardians.has_quorum(&signatures));
+
+        post_signatures_checked(&mut svm, &payer, 0, &signatures, Some(&guardians)).unwrap();
+
+        // The gap index 2 is in range too; only recovery rejects it.
+        let mut gap = signatures.clone();
+        gap[2][0] = 2;
+        post_signatures_checked(&mut svm, &payer, 0, &gap, Some(&guardians)).unwrap();
+
+        let mut past = signatures.clone();
+        past[2][0] = 4;
+        assert!(!guardians.has_quorum(&past));
+        let result = post_signatures_checked(&mut svm, &payer, 0, &past, Some(&guardians));
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::InvalidSignatures(
+                crate::VerifyError::IndexOutOfRange {
+                    position: 2,
+                    index: 4,
+                    guardians: 4,
+                }
+            ))
+        ));
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_close_signatures_batch_reclaims_rent() {
+        use crate::TestVaa;
+
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let payer = Keypair::new();
+        let refund = Pubkey::new_unique();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default()).unwrap();
+
+        let batches: Vec<Vec<[u8; 66]>> = (0..3)
+            .map(|i| TestVaa::new(1, [0xAB; 32], i, vec![]).guardian_signatures(&guardians))
+            .collect();
+        let posted = post_signatures_batch(&mut svm, &payer, 0, &batches).unwrap();
+        let accounts: Vec<Pubkey> = posted.iter().map(|p| p.pubkey).collect();
+
+        let paid: u64 = accounts
+            .iter()
+            .map(|pubkey| svm.get_account(pubkey).unwrap().lamports)
+            .sum();
+
+        let reclaimed = close_signatures_batch(&mut svm, &payer, &accounts, &refund).unwrap();
+
+        assert_eq!(reclaimed, paid);
+        assert_eq!(svm.get_balance(&refund).unwrap(), paid);
+        for pubkey in &accounts {
+            assert_account_closed(&svm, pubkey);
+        }
+    }
+
+    #[test]
+    fn test_create_rent_exempt_account() {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 1_000_000_000).unwrap();
+
+        let account = create_rent_exempt_account(&mut svm, &payer, 100).unwrap();
+
+        let created = svm.get_account(&account.pubkey()).unwrap();
+        assert_eq!(created.data.len(), 100);
+        assert_eq!(created.lamports, rent_exempt_lamports(&svm, 100));
+        assert_rent_exempt(&svm, &account.pubkey());
+    }
+
+    #[test]
+    #[should_panic(expected = "holds 1 lamport(s)")]
+    fn test_assert_rent_exempt_underfunded() {
+        let mut svm = LiteSVM::new();
+        let pubkey = Pubkey::new_unique();
+        svm.set_account(
+            pubkey,
+            Account {
+                lamports: 1,
+                data: vec![0; 100],
+                owner: Pubkey::default(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        assert_rent_exempt(&svm, &pubkey);
+    }
+
+    #[test]
+    fn test_assert_account_closed_passes_for_missing_account() {
+        let svm = LiteSVM::new();
+
+        assert_account_closed(&svm, &Pubkey::new_unique());
+    }
+
+    #[test]
+    #[should_panic(expected = "1000 lamport(s) and 4 byte(s) of data remain")]
+    fn test_assert_account_closed_reports_residue() {
+        let mut svm = LiteSVM::new();
+        let pubkey = Pubkey::new_unique();
+        svm.set_account(
+            pubkey,
+            Account {
+                lamports: 1000,
+                data: vec![0; 4],
+                owner: Pubkey::default(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        assert_account_closed(&svm, &pubkey);
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_assert_programs_loaded() {
+        let mut svm = LiteSVM::new();
+        load_wormhole_programs(&mut svm, WormholeProgramsConfig::default()).unwrap();
+
+        assert_shim_loaded(&svm);
+        assert_core_bridge_loaded(&svm);
+    }
+
+    #[test]
+    #[should_panic(expected = "Verify VAA Shim is not loaded")]
+    fn test_assert_shim_loaded_missing() {
+        assert_shim_loaded(&LiteSVM::new());
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_setup_wormhole_multi() {
+        let mut svm = LiteSVM::new();
+        let old = TestGuardianSet::generate(3, 1);
+        let new = TestGuardianSet::generate(3, 2);
+
+        let installed = setup_wormhole_multi(
+            &mut svm,
+            &[
+                GuardianSetSpec::new(0, &old).expiring_at(1_000),
+                (1, &new).into(),
+            ],
+            WormholeProgramsConfig::default(),
+        )
+        .unwrap();
+
+        assert_eq!(installed.len(), 2);
+        assert_eq!(installed[&0], derive_guardian_set(0));
+        assert_eq!(installed[&1], derive_guardian_set(1));
+        assert_eq!(
+            read_guardian_set_addresses(&svm, 0).unwrap(),
+            old.eth_addresses()
+        );
+        assert_eq!(
+            read_guardian_set_addresses(&svm, 1).unwrap(),
+            new.eth_addresses()
+        );
+
+        let old_data = read_account_data(&svm, &installed[&0].0);
+        assert_eq!(&old_data[old_data.len() - 4..], &1_000u32.to_le_bytes());
+        let new_data = read_account_data(&svm, &installed[&1].0);
+        assert_eq!(&new_data[new_data.len() - 4..], &0u32.to_le_bytes());
+    }
+
+    #[test]
+    fn test_setup_wormhole_multi_rejects_empty() {
+        let mut svm = LiteSVM::new();
+
+        assert!(setup_wormhole_multi(&mut svm, &[], WormholeProgramsConfig::default()).is_err());
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_setup_for_vaa_uses_header_index() {
+        use crate::TestVaa;
+
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        let mut vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+        vaa.guardian_set_index = 5;
+
+        let wormhole = setup_for_vaa(
+            &mut svm,
+            &vaa,
+            &guardians,
+            WormholeProgramsConfig::default(),
+        )
+        .unwrap();
+
+        assert_guardian_set_derived(&wormhole, 5);
+        assert_eq!(
+            read_guardian_set_addresses(&svm, 5).unwrap(),
+            guardians.eth_addresses()
+        );
+    }
+
+    #[test]
+    fn test_setup_for_vaa_rejects_before_touching_svm() {
+        use crate::TestVaa;
+
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+        let untouched = |svm: &LiteSVM| {
+            svm.get_account(&derive_guardian_set(0).0).is_none()
+                && svm.get_account(&VERIFY_VAA_SHIM_PROGRAM_ID).is_none()
+        };
+
+        // An empty set can never reach quorum.
+        let mut svm = LiteSVM::new();
+        let result = setup_for_vaa(
+            &mut svm,
+            &vaa,
+            &TestGuardianSet::new(vec![]),
+            WormholeProgramsConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::InvalidSignatures(
+                crate::VerifyError::NoQuorum {
+                    actual: 0,
+                    required: 1,
+                    guardians: 0,
+                }
+            ))
+        ));
+        assert!(untouched(&svm));
+
+        // The signatures verify, but the set belongs at index 1, not the
+        // header's index 0.
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default()).with_index(1);
+        assert!(vaa.preflight(&guardians).is_ok());
+        let result = setup_for_vaa(
+            &mut svm,
+            &vaa,
+            &guardians,
+            WormholeProgramsConfig::default(),
+        );
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::GuardianSetIndexMismatch {
+                set: 1,
+                requested: 0,
+            })
+        ));
+        assert!(untouched(&svm));
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_guardian_signatures_account_data_matches_post() {
+        use crate::TestVaa;
+
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+        setup_wormhole(&mut svm, &guardians, 7, WormholeProgramsConfig::default()).unwrap();
+
+        let signatures = TestVaa::new(1, [0xAB; 32], 42, vec![]).guardian_signatures(&guardians);
+        let posted = post_signatures(&mut svm, &payer, 7, &signatures).unwrap();
+
+        let data = guardian_signatures_account_data(7, &payer.pubkey(), &signatures);
+        assert_eq!(
+            data.len(),
+            guardian_signatures_account_size(signatures.len())
+        );
+        assert_eq!(svm.get_account(&posted.pubkey).unwrap().data, data);
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_post_signatures_twice_rejects_second_post() {
+        use crate::TestVaa;
+
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+        setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default()).unwrap();
+
+        let signatures = TestVaa::new(1, [0xAB; 32], 42, vec![]).guardian_signatures(&guardians);
+        let (posted, second) = post_signatures_twice(&mut svm, &payer, 0, &signatures).unwrap();
+
+        assert!(second.is_err());
+        assert_eq!(
+            svm.get_account(&posted.pubkey).unwrap().data.len(),
+            guardian_signatures_account_size(signatures.len())
+        );
+        close_signatures(&mut svm, &payer, &posted.pubkey, &payer.pubkey()).unwrap();
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_post_signatures_funded() {
+        use crate::TestVaa;
+
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let payer = Keypair::new();
+        let funder = Keypair::new();
+
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+        svm.airdrop(&funder.pubkey(), 10_000_000_000).unwrap();
+
+        setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default()).unwrap();
+
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3, 4]);
+        let signatures = vaa.guardian_signatures(&guardians);
+
+        let payer_before = svm.get_balance(&payer.pubkey()).unwrap();
+        let funder_before = svm.get_balance(&funder.pubkey()).unwrap();
+
+        let posted = post_signatures_funded(&mut svm, &payer, &funder, 0, &signatures).unwrap();
+
+        let rent = svm.get_account(&posted.pubkey).unwrap().lamports;
+        assert_eq!(
+            svm.get_balance(&funder.pubkey()).unwrap(),
+            funder_before - rent,
+            "funder should pay exactly the signatures account rent"
+        );
+        assert!(
+            payer_before - svm.get_balance(&payer.pubkey()).unwrap() < rent,
+            "payer should only pay the transaction fee"
+        );
+    }
+
+    #[cfg(feature = "bundled-fixtures")]
+    #[test]
+    fn test_upgrade_guardian_set() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        let new_set = TestGuardianSet::generate(5, 2);
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 10_000_000_000).unwrap();
+
+        let wormhole =
+            setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default()).unwrap();
+
+        let upgraded =
+            upgrade_guardian_set(&mut svm, &payer, &wormhole, &guardians, &new_set, 1).unwrap();
+
+        assert_guardian_set_derived(&upgraded, 1);
+
+        // The bridge config now points at the new set.
+        let config = svm.get_account(&CORE_BRIDGE_CONFIG).unwrap();
+        assert_eq!(u32::from_le_bytes(config.data[0..4].try_into().unwrap()), 1);
+
+        // The old set was given an expiration time.
+        let old = svm.get_account(&wormhole.guardian_set).unwrap();
+        let expiration = u32::from_le_bytes(old.data[old.data.len() - 4..].try_into().unwrap());
+        assert_ne!(expiration, 0);
+    }
+
+    #[test]
+    fn test_upgrade_guardian_set_missing_account() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        let payer = Keypair::new();
+
+        let (guardian_set, guardian_set_bump) = derive_guardian_set(0);
+        let wormhole = WormholeAccounts {
+            guardian_set,
+            guardian_set_bump,
+        };
+
+        let err = upgrade_guardian_set(&mut svm, &payer, &wormhole, &guardians, &guardians, 1)
+            .err()
+            .unwrap();
+        assert!(matches!(err, WormholeTestError::AccountNotFound(key) if key == guardian_set));
+    }
+
+    #[test]
+    fn test_upgrade_guardian_set_signer_index_out_of_range() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(CORE_BRIDGE_MAX_SIGNERS + 1, 1);
+        let payer = Keypair::new();
+
+        let (guardian_set, guardian_set_bump) =
+            create_guardian_set_account(&mut svm, &guardians, 0);
+        let wormhole = WormholeAccounts {
+            guardian_set,
+            guardian_set_bump,
+        };
+
+        let err = upgrade_guardian_set(&mut svm, &payer, &wormhole, &guardians, &guardians, 1)
+            .err()
+            .unwrap();
+        assert!(matches!(
+            err,
+            WormholeTestError::SignerIndexOutOfRange { index, max }
+                if index as usize == CORE_BRIDGE_MAX_SIGNERS && max == CORE_BRIDGE_MAX_SIGNERS
+        ));
+    }
+
     #[cfg(feature = "bundled-fixtures")]
     #[test]
     fn test_with_posted_signatures_bracket() {
@@ -1158,9 +3500,125 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_multi_verify_transaction_too_large() {
+        let mut svm = LiteSVM::new();
+        let payer = Keypair::new();
+        let batches = vec![vec![[0u8; 66]]; 8];
+
+        let result = build_multi_verify_transaction(&mut svm, &payer, 0, &batches, |sigs, _| {
+            Instruction::new_with_bytes(
+                Pubkey::new_unique(),
+                &[0u8; 200],
+                vec![solana_sdk::instruction::AccountMeta::new_readonly(
+                    *sigs, false,
+                )],
+            )
+        });
+
+        assert!(matches!(
+            result,
+            Err(WormholeTestError::TransactionTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn test_post_signatures_tx_size() {
+        let single = post_signatures_tx_size(1);
+        assert!(single < PACKET_DATA_SIZE);
+
+        // Each additional signature adds 66 bytes of instruction data
+        assert_eq!(post_signatures_tx_size(3), post_signatures_tx_size(2) + 66);
+
+        // A full 19-guardian set does not fit in one transaction
+        assert!(post_signatures_tx_size(19) > PACKET_DATA_SIZE);
+    }
+
     // Note: with_vaa, with_vaa_unchecked, and message emission are tested in
     // integration tests (tests/verify_vaa_example.rs and tests/emit_message_example.rs).
 
+    #[test]
+    fn test_setup_wormhole_accounts_only() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+
+        let accounts = setup_wormhole_accounts_only(
+            &mut svm,
+            &guardians,
+            0,
+            WormholeAccountsConfig {
+                bridge_config: true,
+                fee_collector: false,
+            },
+        )
+        .unwrap();
+
+        assert_guardian_addresses(&svm, 0, &guardians.eth_addresses());
+        assert!(svm.get_account(&accounts.guardian_set).is_some());
+        assert!(svm.get_account(&CORE_BRIDGE_CONFIG).is_some());
+        assert!(svm.get_account(&CORE_BRIDGE_PROGRAM_ID).is_none());
+    }
+
+    #[test]
+    fn test_assert_guardian_addresses() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        create_guardian_set_account(&mut svm, &guardians, 2);
+
+        assert_guardian_addresses(&svm, 2, &guardians.eth_addresses());
+        assert_eq!(read_guardian_set_addresses(&svm, 3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "guardian set 0 differs at index 1")]
+    fn test_assert_guardian_addresses_mismatch() {
+        let mut svm = LiteSVM::new();
+        let guardians = TestGuardianSet::generate(3, 1);
+        create_guardian_set_account(&mut svm, &guardians, 0);
+
+        let mut expected = guardians.eth_addresses();
+        expected.swap(1, 2);
+        assert_guardian_addresses(&svm, 0, &expected);
+    }
+
+    #[cfg(feature = "borsh")]
+    #[test]
+    fn test_read_borsh() {
+        #[derive(borsh::BorshSerialize, borsh::BorshDeserialize, Debug, PartialEq)]
+        struct Stored {
+            sequence: u64,
+            payload: Vec<u8>,
+        }
+
+        let mut svm = LiteSVM::new();
+        let pubkey = Pubkey::new_unique();
+        let stored = Stored {
+            sequence: 42,
+            payload: vec![1, 2, 3],
+        };
+
+        let mut data = borsh::BorshSerialize::try_to_vec(&stored).unwrap();
+        data.extend_from_slice(&[0u8; 16]); // unused trailing space
+        svm.set_account(
+            pubkey,
+            Account {
+                lamports: 1_000_000,
+                data: data.clone(),
+                owner: Pubkey::new_unique(),
+                executable: false,
+                rent_epoch: 0,
+            },
+        )
+        .unwrap();
+
+        assert_eq!(read_account_data(&svm, &pubkey), data);
+        assert_eq!(read_borsh::<Stored>(&svm, &pubkey).unwrap(), stored);
+        assert!(matches!(
+            read_borsh::<Stored>(&svm, &Pubkey::new_unique()),
+            Err(WormholeTestError::AccountNotFound(_))
+        ));
+    }
+
     #[test]
     fn test_message_event_parsing() {
         // Test parsing MessageEvent from raw bytes
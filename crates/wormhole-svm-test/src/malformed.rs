@@ -0,0 +1,192 @@
+//! Byte-level malformed VAAs for parser robustness tests.
+//!
+//! Unlike [`negative_fixtures`](crate::negative_fixtures), which produce
+//! well-formed VAAs that fail verification, these produce bytes that a VAA
+//! parser (e.g. `wormhole_raw_vaas`) must reject before any signature is
+//! checked. Each [`MalformedKind`] documents the exact inconsistency it
+//! introduces.
+//!
+//! # Example
+//!
+//! ```
+//! use wormhole_svm_test::malformed::{malformed_vaa, MalformedKind};
+//! use wormhole_svm_test::{TestGuardianSet, TestVaa};
+//!
+//! let guardians = TestGuardianSet::generate(5, 1);
+//! let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+//!
+//! let bytes = malformed_vaa(
+//!     &vaa,
+//!     &guardians,
+//!     MalformedKind::SignatureCountLie { declared: 5, actual: 3 },
+//! );
+//! assert_eq!(bytes[5], 5);
+//! ```
+
+use crate::{vaa_header_bytes, TestGuardianSet, TestVaa, VAA_BODY_HEADER_LEN};
+
+/// A specific way to corrupt a signed VAA.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MalformedKind {
+    /// The signature count byte says `declared`, but `actual` signatures
+    /// (from the first `actual` guardians) follow.
+    ///
+    /// The body is unchanged, so a parser that trusts the count reads the body
+    /// from the wrong offset: with `declared > actual` it consumes
+    /// `66 * (declared - actual)` body bytes as signatures (or runs off the
+    /// end), and with `declared < actual` it treats the surplus signatures as
+    /// the start of the body.
+    SignatureCountLie { declared: u8, actual: usize },
+    /// The signed VAA cut off after `offset` bytes (see
+    /// [`truncate_at`]).
+    Truncated { offset: usize },
+}
+
+/// The first `offset` bytes of a signed VAA (all of it if `offset` is past the end).
+///
+/// Pair with [`truncation_offsets`] to walk every parser branch.
+pub fn truncate_at(bytes: &[u8], offset: usize) -> Vec<u8> {
+    bytes[..offset.min(bytes.len())].to_vec()
+}
+
+/// The interesting offsets at which to truncate the signed VAA `bytes`.
+///
+/// Each one stops a parser in a different place, in increasing order:
+///
+/// - `0`: empty input
+/// - `1`: after the version, before the guardian set index
+/// - `3`: mid guardian set index
+/// - `5`: after the guardian set index, before the signature count
+/// - `6`: after the signature count, before the first signature (only if
+///   the count is non-zero)
+/// - `6 + 33`: mid first signature (only if the count is non-zero)
+/// - end of signatures: the body is missing entirely
+/// - end of signatures + 25: mid body header (mid emitter address)
+/// - end of signatures + 50: one byte short of the body header
+/// - end of signatures + 51: body header complete, payload missing (only
+///   if the payload is non-empty)
+///
+/// Offsets at or past the end of `bytes` are skipped, so every returned
+/// offset yields a strictly shorter VAA.
+pub fn truncation_offsets(bytes: &[u8]) -> Vec<usize> {
+    let mut offsets = vec![0, 1, 3, 5];
+
+    let num_signatures = bytes.get(5).copied().unwrap_or(0) as usize;
+    if num_signatures > 0 {
+        offsets.extend([6, 6 + 33]);
+    }
+
+    let body_start = 6 + 66 * num_signatures;
+    offsets.extend([
+        body_start,
+        body_start + 25,
+        body_start + VAA_BODY_HEADER_LEN - 1,
+        body_start + VAA_BODY_HEADER_LEN,
+    ]);
+
+    offsets.retain(|&offset| offset < bytes.len());
+    offsets
+}
+
+/// Sign `vaa` with `guardians` and corrupt the result as described by `kind`.
+///
+/// # Panics
+///
+/// Panics if `kind` needs more signatures than `guardians` can provide.
+pub fn malformed_vaa(vaa: &TestVaa, guardians: &TestGuardianSet, kind: MalformedKind) -> Vec<u8> {
+    match kind {
+        MalformedKind::SignatureCountLie { declared, actual } => {
+            assert!(
+                actual <= guardians.len(),
+                "SignatureCountLie needs {} signature(s) but the set has {} guardian(s)",
+                actual,
+                guardians.len()
+            );
+            let signatures = &vaa.guardian_signatures(guardians)[..actual];
+
+            let mut bytes = vaa_header_bytes(vaa.version, vaa.guardian_set_index, signatures);
+            bytes[5] = declared;
+            bytes.extend_from_slice(&vaa.body());
+            bytes
+        }
+        MalformedKind::Truncated { offset } => truncate_at(&vaa.sign(guardians), offset),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VerifyError;
+
+    #[test]
+    fn test_signature_count_lie() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        let overstated = malformed_vaa(
+            &vaa,
+            &guardians,
+            MalformedKind::SignatureCountLie {
+                declared: 5,
+                actual: 3,
+            },
+        );
+        assert_eq!(overstated[5], 5);
+        assert_eq!(overstated.len(), 6 + 66 * 3 + vaa.body().len());
+        assert!(matches!(
+            crate::verify::parse_signed_vaa(&overstated),
+            Err(VerifyError::MalformedVaa(_))
+        ));
+
+        let understated = malformed_vaa(
+            &vaa,
+            &guardians,
+            MalformedKind::SignatureCountLie {
+                declared: 1,
+                actual: 3,
+            },
+        );
+        let (_, signatures, body) = crate::verify::parse_signed_vaa(&understated).unwrap();
+        assert_eq!(signatures.len(), 1);
+        assert_ne!(body, vaa.body().as_slice());
+    }
+
+    #[test]
+    fn test_truncation_offsets() {
+        let guardians = TestGuardianSet::generate(2, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let signed = vaa.sign(&guardians);
+
+        let body_start = 6 + 66 * 2;
+        assert_eq!(
+            truncation_offsets(&signed),
+            vec![
+                0,
+                1,
+                3,
+                5,
+                6,
+                39,
+                body_start,
+                body_start + 25,
+                body_start + 50,
+                body_start + 51
+            ]
+        );
+
+        for offset in truncation_offsets(&signed) {
+            let truncated = malformed_vaa(&vaa, &guardians, MalformedKind::Truncated { offset });
+            assert_eq!(truncated, &signed[..offset]);
+            assert!(truncated.len() < signed.len());
+        }
+    }
+
+    #[test]
+    fn test_truncation_offsets_skip_empty_payload() {
+        let guardians = TestGuardianSet::generate(1, 1);
+        let signed = TestVaa::new(1, [0xAB; 32], 42, vec![]).sign(&guardians);
+
+        let offsets = truncation_offsets(&signed);
+        assert_eq!(offsets.last(), Some(&(6 + 66 + 50)));
+    }
+}
@@ -0,0 +1,318 @@
+//! Ready-made VAA fixtures that the Verify VAA Shim must reject.
+//!
+//! Each constructor takes a base [`TestVaa`] and the guardian set that would
+//! normally sign it, and returns a [`NegativeFixture`]: the VAA, the guardian
+//! signatures to post, and the guardian set / clock state needed to reproduce
+//! one specific rejection. With the `litesvm` feature,
+//! [`NegativeFixture::install`] writes that state into a LiteSVM instance.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use wormhole_svm_test::negative_fixtures;
+//!
+//! let fixture = negative_fixtures::expired_set_vaa(&vaa, &guardians);
+//! let wormhole = fixture.install(&mut svm);
+//!
+//! let posted = post_signatures(&mut svm, &payer, fixture.guardian_set_index, &fixture.signatures)?;
+//! // Calling verify_hash with `fixture.vaa_body` must now fail.
+//! ```
+
+use crate::{TestGuardianSet, TestVaa, UNKNOWN_GUARDIAN_SET_INDEX};
+
+/// Expiration time written into the guardian set by [`expired_set_vaa`].
+pub const EXPIRED_SET_EXPIRATION_TIME: u32 = 1_000;
+
+/// Logged by the Verify VAA Shim when the guardian set has expired.
+pub const SHIM_LOG_GUARDIAN_SET_EXPIRED: &str = "Guardian set (account #1) is expired";
+
+/// Logged by the Verify VAA Shim when there are fewer signatures than quorum.
+pub const SHIM_LOG_NO_QUORUM: &str = "Guardian signatures (account #2) fails to meet quorum";
+
+/// Logged by the Verify VAA Shim when a signature does not recover the key at
+/// its guardian index. The full line is `Guardian signature index {i} does
+/// not recover guardian {i} pubkey`.
+pub const SHIM_LOG_KEY_MISMATCH: &str = "does not recover guardian";
+
+/// Logged when the guardian set account does not exist. The shim does not
+/// check for this case; deserializing the empty account panics, and the panic
+/// message is logged.
+pub const SHIM_LOG_GUARDIAN_SET_MISSING: &str = "called `Option::unwrap()` on a `None` value";
+
+/// Logged by the Verify VAA Shim when the guardian set account is not the PDA
+/// derived from the signatures' guardian set index and the given bump.
+pub const SHIM_LOG_GUARDIAN_SET_SEEDS: &str = "Guardian set (account #1) seeds constraint violated";
+
+/// Logged by the Verify VAA Shim when the given bump does not derive a valid
+/// guardian set PDA at all.
+pub const SHIM_LOG_GUARDIAN_SET_ADDRESS: &str = "Guardian set (account #1) address creation failed";
+
+/// Seed used to generate the unrelated guardian set in [`wrong_index_vaa`].
+const WRONG_INDEX_SEED: u64 = 0x5752_4f4e_4749_4458;
+
+/// A guardian set that must be installed for a fixture to reproduce.
+#[derive(Clone)]
+pub struct FixtureGuardianSet {
+    /// The guardian set index.
+    pub index: u32,
+    /// The guardians in the set.
+    pub guardians: TestGuardianSet,
+    /// Expiration time written into the account (0 = never expires).
+    pub expiration_time: u32,
+}
+
+/// A VAA paired with the setup needed to make the Verify VAA Shim reject it.
+#[derive(Clone)]
+pub struct NegativeFixture {
+    /// The serialized VAA (header, signatures, body).
+    pub vaa_bytes: Vec<u8>,
+    /// The VAA body to pass to the verifying program.
+    pub vaa_body: Vec<u8>,
+    /// Guardian set index the signatures are posted under.
+    pub guardian_set_index: u32,
+    /// Guardian signatures to post.
+    pub signatures: Vec<[u8; 66]>,
+    /// Guardian sets to install before posting.
+    pub guardian_sets: Vec<FixtureGuardianSet>,
+    /// Clock `unix_timestamp` to set before verifying, if any.
+    pub unix_timestamp: Option<i64>,
+    /// The line the Verify VAA Shim logs when it rejects this fixture, one of
+    /// the `SHIM_LOG_*` constants. Match it as a substring of the
+    /// transaction's logs.
+    pub expected_error: &'static str,
+}
+
+/// A VAA signed by a guardian set that has already expired.
+///
+/// The set is installed with `expiration_time = EXPIRED_SET_EXPIRATION_TIME`
+/// and the clock is moved one second past it, so `verify_hash` fails the
+/// guardian set expiration check ([`SHIM_LOG_GUARDIAN_SET_EXPIRED`]).
+pub fn expired_set_vaa(vaa: &TestVaa, guardians: &TestGuardianSet) -> NegativeFixture {
+    let signatures = vaa.guardian_signatures(guardians);
+
+    NegativeFixture {
+        vaa_bytes: vaa.sign(guardians),
+        vaa_body: vaa.body(),
+        guardian_set_index: vaa.guardian_set_index,
+        signatures,
+        guardian_sets: vec![FixtureGuardianSet {
+            index: vaa.guardian_set_index,
+            guardians: guardians.clone(),
+            expiration_time: EXPIRED_SET_EXPIRATION_TIME,
+        }],
+        unix_timestamp: Some(EXPIRED_SET_EXPIRATION_TIME as i64 + 1),
+        expected_error: SHIM_LOG_GUARDIAN_SET_EXPIRED,
+    }
+}
+
+/// A VAA signed by `guardians` but claiming the next guardian set index.
+///
+/// Both sets are installed: `guardians` at the VAA's index and an unrelated
+/// set at `index + 1`. The VAA header and the posted signatures reference
+/// `index + 1`, so `verify_hash` recovers keys that are not members of that
+/// set and fails the guardian key check ([`SHIM_LOG_KEY_MISMATCH`]).
+pub fn wrong_index_vaa(vaa: &TestVaa, guardians: &TestGuardianSet) -> NegativeFixture {
+    let wrong_index = vaa.guardian_set_index.wrapping_add(1);
+    let wrong_vaa = TestVaa {
+        guardian_set_index: wrong_index,
+        ..vaa.clone()
+    };
+
+    NegativeFixture {
+        vaa_bytes: wrong_vaa.sign(guardians),
+        vaa_body: wrong_vaa.body(),
+        guardian_set_index: wrong_index,
+        signatures: wrong_vaa.guardian_signatures(guardians),
+        guardian_sets: vec![
+            FixtureGuardianSet {
+                index: vaa.guardian_set_index,
+                guardians: guardians.clone(),
+                expiration_time: 0,
+            },
+            FixtureGuardianSet {
+                index: wrong_index,
+                guardians: TestGuardianSet::generate(guardians.len(), WRONG_INDEX_SEED),
+                expiration_time: 0,
+            },
+        ],
+        unix_timestamp: None,
+        expected_error: SHIM_LOG_KEY_MISMATCH,
+    }
+}
+
+/// A VAA whose header references a guardian set that was never installed.
+///
+/// Only `guardians` at the VAA's own index is installed; the header and the
+/// posted signatures reference [`UNKNOWN_GUARDIAN_SET_INDEX`], whose guardian
+/// set PDA does not exist, so `verify_hash` fails to load the set
+/// ([`SHIM_LOG_GUARDIAN_SET_MISSING`]). Unlike [`wrong_index_vaa`], no
+/// account exists at the referenced index at all.
+pub fn unknown_set_vaa(vaa: &TestVaa, guardians: &TestGuardianSet) -> NegativeFixture {
+    let unknown = vaa.with_unknown_set_index(UNKNOWN_GUARDIAN_SET_INDEX);
+
+    NegativeFixture {
+        vaa_bytes: unknown.sign(guardians),
+        vaa_body: unknown.body(),
+        guardian_set_index: UNKNOWN_GUARDIAN_SET_INDEX,
+        signatures: unknown.guardian_signatures(guardians),
+        guardian_sets: vec![FixtureGuardianSet {
+            index: vaa.guardian_set_index,
+            guardians: guardians.clone(),
+            expiration_time: 0,
+        }],
+        unix_timestamp: None,
+        expected_error: SHIM_LOG_GUARDIAN_SET_MISSING,
+    }
+}
+
+/// A VAA signed by one fewer guardian than quorum.
+///
+/// Signs with the first `quorum(n) - 1` guardians, so `verify_hash` fails the
+/// quorum check ([`SHIM_LOG_NO_QUORUM`]). For a single-guardian set this yields zero signatures, which
+/// the shim already rejects when posting.
+pub fn insufficient_quorum_vaa(vaa: &TestVaa, guardians: &TestGuardianSet) -> NegativeFixture {
+    let indices: Vec<u8> = (0..guardians.quorum() - 1).map(|i| i as u8).collect();
+
+    NegativeFixture {
+        vaa_bytes: vaa.sign_with(guardians, &indices),
+        vaa_body: vaa.body(),
+        guardian_set_index: vaa.guardian_set_index,
+        signatures: vaa.sign_below_quorum(guardians),
+        guardian_sets: vec![FixtureGuardianSet {
+            index: vaa.guardian_set_index,
+            guardians: guardians.clone(),
+            expiration_time: 0,
+        }],
+        unix_timestamp: None,
+        expected_error: SHIM_LOG_NO_QUORUM,
+    }
+}
+
+/// A VAA whose payload was modified after signing.
+///
+/// The signatures are valid for the original body, but the last payload byte
+/// of the submitted body is flipped (or a byte is appended to an empty
+/// payload). The digest no longer matches, so `verify_hash` recovers the wrong
+/// keys and fails the guardian key check ([`SHIM_LOG_KEY_MISMATCH`]).
+pub fn tampered_vaa(vaa: &TestVaa, guardians: &TestGuardianSet) -> NegativeFixture {
+    let signatures = vaa.guardian_signatures(guardians);
+
+    let mut tampered = vaa.clone();
+    match tampered.payload.last_mut() {
+        Some(byte) => *byte ^= 0xFF,
+        None => tampered.payload.push(0xFF),
+    }
+    let tampered_body = tampered.body();
+
+    let mut vaa_bytes = vaa.sign(guardians);
+    let header_len = vaa_bytes.len() - vaa.body().len();
+    vaa_bytes.truncate(header_len);
+    vaa_bytes.extend_from_slice(&tampered_body);
+
+    NegativeFixture {
+        vaa_bytes,
+        vaa_body: tampered_body,
+        guardian_set_index: vaa.guardian_set_index,
+        signatures,
+        guardian_sets: vec![FixtureGuardianSet {
+            index: vaa.guardian_set_index,
+            guardians: guardians.clone(),
+            expiration_time: 0,
+        }],
+        unix_timestamp: None,
+        expected_error: SHIM_LOG_KEY_MISMATCH,
+    }
+}
+
+#[cfg(feature = "litesvm")]
+impl NegativeFixture {
+    /// Install the fixture's guardian sets and clock into LiteSVM.
+    ///
+    /// Wormhole programs and the bridge config must already be set up (e.g.
+    /// via `setup_wormhole`). Returns the guardian set PDA for
+    /// [`guardian_set_index`](Self::guardian_set_index), which is the account
+    /// to pass to the verifying program.
+    pub fn install(&self, svm: &mut litesvm::LiteSVM) -> crate::WormholeAccounts {
+        use solana_sdk::clock::Clock;
+
+        for set in &self.guardian_sets {
+            crate::create_guardian_set_account_with_expiration(
+                svm,
+                &set.guardians,
+                set.index,
+                set.expiration_time,
+            );
+        }
+
+        if let Some(unix_timestamp) = self.unix_timestamp {
+            let mut clock = svm.get_sysvar::<Clock>();
+            clock.unix_timestamp = unix_timestamp;
+            svm.set_sysvar(&clock);
+        }
+
+        let (guardian_set, guardian_set_bump) = crate::derive_guardian_set(self.guardian_set_index);
+
+        crate::WormholeAccounts {
+            guardian_set,
+            guardian_set_bump,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_vaa() -> TestVaa {
+        TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3, 4])
+    }
+
+    #[test]
+    fn test_insufficient_quorum_signs_one_below_quorum() {
+        let guardians = TestGuardianSet::generate(13, 1);
+        let fixture = insufficient_quorum_vaa(&base_vaa(), &guardians);
+
+        assert_eq!(fixture.signatures.len(), crate::quorum(13) - 1);
+        assert_eq!(fixture.vaa_bytes[5] as usize, crate::quorum(13) - 1);
+    }
+
+    #[test]
+    fn test_tampered_body_differs_from_signed_body() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = base_vaa();
+        let fixture = tampered_vaa(&vaa, &guardians);
+
+        assert_ne!(fixture.vaa_body, vaa.body());
+        assert_eq!(fixture.signatures, vaa.guardian_signatures(&guardians));
+        assert!(fixture.vaa_bytes.ends_with(&fixture.vaa_body));
+    }
+
+    #[test]
+    fn test_unknown_set_is_not_installed() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let fixture = unknown_set_vaa(&base_vaa(), &guardians);
+
+        assert_eq!(fixture.guardian_set_index, UNKNOWN_GUARDIAN_SET_INDEX);
+        assert_eq!(
+            fixture.vaa_bytes[1..5],
+            UNKNOWN_GUARDIAN_SET_INDEX.to_be_bytes()
+        );
+        assert!(fixture
+            .guardian_sets
+            .iter()
+            .all(|set| set.index != UNKNOWN_GUARDIAN_SET_INDEX));
+    }
+
+    #[test]
+    fn test_wrong_index_installs_both_sets() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let fixture = wrong_index_vaa(&base_vaa(), &guardians);
+
+        assert_eq!(fixture.guardian_set_index, 1);
+        assert_eq!(fixture.guardian_sets.len(), 2);
+        assert_ne!(
+            fixture.guardian_sets[0].guardians.eth_addresses(),
+            fixture.guardian_sets[1].guardians.eth_addresses()
+        );
+    }
+}
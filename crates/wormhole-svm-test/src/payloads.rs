@@ -0,0 +1,285 @@
+//! Payload constructors for well-known Wormhole message formats.
+//!
+//! These produce the raw payload bytes to pass to [`TestVaa::new`](crate::TestVaa::new).
+//!
+//! # Governance
+//!
+//! Governance payloads are laid out as:
+//!
+//! ```text
+//! module (32) | action (1) | target_chain (2, big-endian) | action-specific args
+//! ```
+//!
+//! A target chain of [`ALL_CHAINS`] (0) applies the action on every chain;
+//! a specific chain ID (e.g. [`SOLANA_CHAIN`] for [`solana_governance`]) limits
+//! it to that chain. Programs enforce this distinction, so both are exposed.
+//!
+//! Governance VAAs must be emitted by [`GOVERNANCE_EMITTER`] on
+//! [`GOVERNANCE_CHAIN`].
+
+/// Governance target chain meaning "every chain".
+pub const ALL_CHAINS: u16 = 0;
+
+/// Wormhole chain ID for Solana.
+pub const SOLANA_CHAIN: u16 = 1;
+
+/// Emitter chain of governance VAAs (Solana).
+pub const GOVERNANCE_CHAIN: u16 = 1;
+
+/// Emitter address of governance VAAs: `0x00..04`.
+pub const GOVERNANCE_EMITTER: [u8; 32] = {
+    let mut emitter = [0u8; 32];
+    emitter[31] = 4;
+    emitter
+};
+
+/// Governance module identifier for the Core Bridge: `"Core"` right-aligned
+/// in 32 bytes.
+pub const CORE_MODULE: [u8; 32] = right_aligned(b"Core");
+
+/// Governance module identifier for the Token Bridge: `"TokenBridge"`
+/// right-aligned in 32 bytes.
+pub const TOKEN_BRIDGE_MODULE: [u8; 32] = right_aligned(b"TokenBridge");
+
+/// Core Bridge governance action that installs a new guardian set.
+pub const CORE_ACTION_GUARDIAN_SET_UPGRADE: u8 = 2;
+
+/// Core Bridge governance action that upgrades the contract.
+pub const CORE_ACTION_CONTRACT_UPGRADE: u8 = 1;
+
+/// Contract upgrade action used by the other governance modules.
+///
+/// `TokenBridge`, `NFTBridge` and `WormholeRelayer` reserve action 1 for
+/// registering a chain, so their upgrade is action 2.
+pub const MODULE_ACTION_CONTRACT_UPGRADE: u8 = 2;
+
+/// Right-align an ASCII module name into 32 bytes, zero-padded on the left.
+const fn right_aligned(name: &[u8]) -> [u8; 32] {
+    let mut module = [0u8; 32];
+    let mut i = 0;
+    while i < name.len() {
+        module[32 - name.len() + i] = name[i];
+        i += 1;
+    }
+    module
+}
+
+/// Build a governance payload for `module` and `action` targeting `target_chain`.
+pub fn governance(module: [u8; 32], action: u8, target_chain: u16, args: &[u8]) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(32 + 1 + 2 + args.len());
+    payload.extend_from_slice(&module);
+    payload.push(action);
+    payload.extend_from_slice(&target_chain.to_be_bytes());
+    payload.extend_from_slice(args);
+    payload
+}
+
+/// Build a governance payload that applies on every chain (target chain 0).
+pub fn global_governance(module: [u8; 32], action: u8, args: &[u8]) -> Vec<u8> {
+    governance(module, action, ALL_CHAINS, args)
+}
+
+/// Build a governance payload that only applies on Solana (target chain 1).
+///
+/// Use this for Solana-specific actions such as setting the message fee on
+/// the Solana Core Bridge.
+pub fn solana_governance(module: [u8; 32], action: u8, args: &[u8]) -> Vec<u8> {
+    governance(module, action, SOLANA_CHAIN, args)
+}
+
+/// Build a Core Bridge guardian set upgrade payload (applies on every chain).
+///
+/// Args are laid out as `new_index (4, big-endian) | num_keys (1) | keys (20 each)`.
+pub fn guardian_set_upgrade(new_index: u32, new_guardians: &[[u8; 20]]) -> Vec<u8> {
+    let mut args = Vec::with_capacity(4 + 1 + 20 * new_guardians.len());
+    args.extend_from_slice(&new_index.to_be_bytes());
+    args.push(new_guardians.len() as u8);
+    for address in new_guardians {
+        args.extend_from_slice(address);
+    }
+
+    global_governance(CORE_MODULE, CORE_ACTION_GUARDIAN_SET_UPGRADE, &args)
+}
+
+/// The contract upgrade action number for `module`.
+///
+/// | Module | Action |
+/// |---|---|
+/// | `Core` | [`CORE_ACTION_CONTRACT_UPGRADE`] (1) |
+/// | `TokenBridge`, `NFTBridge`, `WormholeRelayer`, others | [`MODULE_ACTION_CONTRACT_UPGRADE`] (2) |
+pub fn contract_upgrade_action(module: &[u8; 32]) -> u8 {
+    if *module == CORE_MODULE {
+        CORE_ACTION_CONTRACT_UPGRADE
+    } else {
+        MODULE_ACTION_CONTRACT_UPGRADE
+    }
+}
+
+/// Build a contract upgrade payload for `module` on `target_chain`.
+///
+/// The only arg is the 32-byte address of the new implementation (on Solana,
+/// the buffer account holding the new program). The action number comes from
+/// [`contract_upgrade_action`].
+pub fn contract_upgrade(
+    module: [u8; 32],
+    target_chain: u16,
+    new_implementation: [u8; 32],
+) -> Vec<u8> {
+    governance(
+        module,
+        contract_upgrade_action(&module),
+        target_chain,
+        &new_implementation,
+    )
+}
+
+/// Token Bridge payload ID of a plain transfer.
+pub const TOKEN_BRIDGE_TRANSFER_PAYLOAD_ID: u8 = 1;
+
+/// Build a Token Bridge transfer payload (payload ID 1).
+///
+/// Laid out big-endian as:
+///
+/// ```text
+/// payload_id (1) | amount (32) | token_address (32) | token_chain (2)
+///   | recipient (32) | recipient_chain (2) | fee (32)
+/// ```
+///
+/// `amount` and `fee` are uint256 on the wire; the `u128` values fill the
+/// low 16 bytes. The amount is in the token's normalized (at most 8 decimal)
+/// units, as the Token Bridge encodes it.
+pub fn token_bridge_transfer(
+    amount: u128,
+    token_address: [u8; 32],
+    token_chain: u16,
+    recipient: [u8; 32],
+    recipient_chain: u16,
+    fee: u128,
+) -> Vec<u8> {
+    let mut payload = Vec::with_capacity(133);
+    payload.push(TOKEN_BRIDGE_TRANSFER_PAYLOAD_ID);
+    payload.extend_from_slice(&u256_be(amount));
+    payload.extend_from_slice(&token_address);
+    payload.extend_from_slice(&token_chain.to_be_bytes());
+    payload.extend_from_slice(&recipient);
+    payload.extend_from_slice(&recipient_chain.to_be_bytes());
+    payload.extend_from_slice(&u256_be(fee));
+    payload
+}
+
+/// Widen `value` to a 32-byte big-endian uint256.
+fn u256_be(value: u128) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[16..].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_governance_layout() {
+        let module = [0x11; 32];
+        let payload = governance(module, 3, 0x0102, &[0xAA, 0xBB]);
+
+        assert_eq!(payload.len(), 32 + 1 + 2 + 2);
+        assert_eq!(&payload[0..32], &module);
+        assert_eq!(payload[32], 3);
+        assert_eq!(u16::from_be_bytes([payload[33], payload[34]]), 0x0102);
+        assert_eq!(&payload[35..], &[0xAA, 0xBB]);
+    }
+
+    #[test]
+    fn test_module_identifiers() {
+        let mut core = [0u8; 32];
+        core[28..].copy_from_slice(b"Core");
+        assert_eq!(CORE_MODULE, core);
+
+        let mut token_bridge = [0u8; 32];
+        token_bridge[21..].copy_from_slice(b"TokenBridge");
+        assert_eq!(TOKEN_BRIDGE_MODULE, token_bridge);
+
+        let payload = governance(TOKEN_BRIDGE_MODULE, 1, SOLANA_CHAIN, &[]);
+        assert_eq!(&payload[0..32], &token_bridge);
+    }
+
+    #[test]
+    fn test_governance_target_chains() {
+        let module = [0x11; 32];
+
+        let global = global_governance(module, 1, &[]);
+        assert_eq!(u16::from_be_bytes([global[33], global[34]]), ALL_CHAINS);
+
+        let solana = solana_governance(module, 1, &[]);
+        assert_eq!(u16::from_be_bytes([solana[33], solana[34]]), SOLANA_CHAIN);
+    }
+
+    #[test]
+    fn test_governance_emitter() {
+        assert_eq!(GOVERNANCE_CHAIN, 1);
+        assert_eq!(GOVERNANCE_EMITTER[31], 4);
+        assert!(GOVERNANCE_EMITTER[..31].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn test_guardian_set_upgrade_layout() {
+        let keys = [[0x01; 20], [0x02; 20]];
+        let payload = guardian_set_upgrade(7, &keys);
+
+        assert_eq!(&payload[0..32], &CORE_MODULE);
+        assert_eq!(payload[32], CORE_ACTION_GUARDIAN_SET_UPGRADE);
+        assert_eq!(u16::from_be_bytes([payload[33], payload[34]]), ALL_CHAINS);
+        assert_eq!(u32::from_be_bytes(payload[35..39].try_into().unwrap()), 7);
+        assert_eq!(payload[39], 2);
+        assert_eq!(&payload[40..60], &keys[0]);
+        assert_eq!(&payload[60..80], &keys[1]);
+    }
+
+    #[test]
+    fn test_contract_upgrade_layout() {
+        let implementation = [0x42; 32];
+
+        let core = contract_upgrade(CORE_MODULE, SOLANA_CHAIN, implementation);
+        assert_eq!(core.len(), 32 + 1 + 2 + 32);
+        assert_eq!(&core[0..32], &CORE_MODULE);
+        assert_eq!(core[32], CORE_ACTION_CONTRACT_UPGRADE);
+        assert_eq!(u16::from_be_bytes([core[33], core[34]]), SOLANA_CHAIN);
+        assert_eq!(&core[35..], &implementation);
+
+        let token_bridge = contract_upgrade(TOKEN_BRIDGE_MODULE, 2, implementation);
+        assert_eq!(token_bridge[32], MODULE_ACTION_CONTRACT_UPGRADE);
+    }
+
+    #[test]
+    fn test_token_bridge_transfer_layout() {
+        let payload = token_bridge_transfer(
+            1_000_000,
+            [0x11; 32],
+            2,
+            [0x22; 32],
+            SOLANA_CHAIN,
+            u128::MAX,
+        );
+
+        assert_eq!(payload.len(), 133);
+        assert_eq!(payload[0], TOKEN_BRIDGE_TRANSFER_PAYLOAD_ID);
+        assert_eq!(&payload[1..17], &[0u8; 16]);
+        assert_eq!(
+            u128::from_be_bytes(payload[17..33].try_into().unwrap()),
+            1_000_000
+        );
+        assert_eq!(&payload[33..65], &[0x11; 32]);
+        assert_eq!(u16::from_be_bytes([payload[65], payload[66]]), 2);
+        assert_eq!(&payload[67..99], &[0x22; 32]);
+        assert_eq!(
+            u16::from_be_bytes([payload[99], payload[100]]),
+            SOLANA_CHAIN
+        );
+        assert_eq!(&payload[101..117], &[0u8; 16]);
+        assert_eq!(
+            u128::from_be_bytes(payload[117..133].try_into().unwrap()),
+            u128::MAX
+        );
+    }
+}
@@ -15,21 +15,28 @@
 //!     10,
 //! ).expect("resolution should succeed");
 //!
-//! assert_eq!(result.iterations, 2);
+//! assert_resolved_in(&result, 2);
 //! ```
 
+use std::cell::{Cell, RefCell};
+
 use litesvm::LiteSVM;
 use solana_sdk::{
+    account::Account,
+    hash::Hash,
     pubkey::Pubkey,
     signature::{Keypair, Signature},
+    transaction::Transaction,
 };
+use wormhole_svm_submit::SolanaConnection;
 
-use crate::litesvm::{LiteSvmConnection, WormholeTestError};
+use crate::litesvm::{LiteSvmConnection, LiteSvmError, WormholeTestError};
 use crate::TestGuardianSet;
 
 // Re-export types consumers need for inspecting resolved instructions.
 pub use wormhole_svm_submit::resolve::{
-    InstructionGroup, ResolverResult, SerializableAccountMeta, SerializableInstruction,
+    build_resolver_instruction_data, InstructionGroup, ResolveOptions, ResolverResult,
+    SerializableAccountMeta, SerializableInstruction,
 };
 pub use wormhole_svm_submit::{
     SubmitError, RESOLVER_PUBKEY_GUARDIAN_SET, RESOLVER_PUBKEY_PAYER, RESOLVER_PUBKEY_SHIM_VAA_SIGS,
@@ -68,6 +75,149 @@ pub fn resolve_execute_vaa_v1(
     .map_err(|e| e.to_string())
 }
 
+/// Like [`resolve_execute_vaa_v1`], with diagnostic [`ResolveOptions`].
+///
+/// Set [`ResolveOptions::capture_return_data`] to inspect the raw bytes a
+/// resolver returned, e.g. when they fail to deserialize.
+pub fn resolve_execute_vaa_v1_with_options(
+    svm: &mut LiteSVM,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    vaa_body: &[u8],
+    guardian_set: &Pubkey,
+    max_iterations: usize,
+    options: ResolveOptions,
+) -> Result<ResolverResult, String> {
+    let conn = LiteSvmConnection(svm);
+    wormhole_svm_submit::resolve::resolve_execute_vaa_v1_with_options(
+        &conn,
+        program_id,
+        payer,
+        vaa_body,
+        guardian_set,
+        max_iterations,
+        options,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Like [`resolve_execute_vaa_v1`], calling `between_iterations` on the SVM
+/// before every resolver iteration after the first.
+///
+/// Use it to change state mid-resolution, e.g. rotate the guardian set or
+/// close an account the program already asked for, and check the resolver
+/// copes with it.
+pub fn resolve_execute_vaa_v1_with_hook<F>(
+    svm: &mut LiteSVM,
+    program_id: &Pubkey,
+    payer: &Keypair,
+    vaa_body: &[u8],
+    guardian_set: &Pubkey,
+    max_iterations: usize,
+    between_iterations: F,
+) -> Result<ResolverResult, String>
+where
+    F: FnMut(&mut LiteSVM),
+{
+    let conn = HookedConnection::new(svm, between_iterations);
+    wormhole_svm_submit::resolve::resolve_execute_vaa_v1(
+        &conn,
+        program_id,
+        payer,
+        vaa_body,
+        guardian_set,
+        max_iterations,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// LiteSVM connection that runs a hook before each resolver iteration but the first.
+///
+/// The resolver loop fetches a blockhash at the start of every iteration, so
+/// the hook runs there; a hook that expires the blockhash still produces a
+/// valid simulation.
+struct HookedConnection<'a, F> {
+    svm: RefCell<&'a mut LiteSVM>,
+    hook: RefCell<F>,
+    iterations: Cell<usize>,
+}
+
+impl<'a, F: FnMut(&mut LiteSVM)> HookedConnection<'a, F> {
+    fn new(svm: &'a mut LiteSVM, hook: F) -> Self {
+        Self {
+            svm: RefCell::new(svm),
+            hook: RefCell::new(hook),
+            iterations: Cell::new(0),
+        }
+    }
+}
+
+impl<F: FnMut(&mut LiteSVM)> SolanaConnection for HookedConnection<'_, F> {
+    type Error = LiteSvmError;
+
+    fn get_latest_blockhash(&self) -> Result<Hash, Self::Error> {
+        let mut svm = self.svm.borrow_mut();
+        if self.iterations.get() > 0 {
+            (self.hook.borrow_mut())(&mut **svm);
+        }
+        self.iterations.set(self.iterations.get() + 1);
+        Ok(svm.latest_blockhash())
+    }
+
+    fn simulate_return_data(&self, tx: &Transaction) -> Result<Option<Vec<u8>>, Self::Error> {
+        LiteSvmConnection(&mut self.svm.borrow_mut()).simulate_return_data(tx)
+    }
+
+    fn send_and_confirm(&mut self, tx: &Transaction) -> Result<Signature, Self::Error> {
+        LiteSvmConnection(self.svm.get_mut()).send_and_confirm(tx)
+    }
+
+    fn get_account(&self, pubkey: &Pubkey) -> Result<Option<Account>, Self::Error> {
+        Ok(self.svm.borrow().get_account(pubkey))
+    }
+}
+
+/// Assert that the resolver finished in exactly `expected` iterations.
+///
+/// The iteration count is the number of account-discovery rounds the program
+/// needed, so pinning it guards against regressions in resolver behavior.
+///
+/// # Panics
+///
+/// Panics with the actual iteration count and the number of resolved
+/// instruction groups if it differs from `expected`.
+pub fn assert_resolved_in(result: &ResolverResult, expected: usize) {
+    assert!(
+        result.iterations == expected,
+        "resolver resolved in {} iteration(s), expected {} ({} instruction group(s) resolved)",
+        result.iterations,
+        expected,
+        result.instruction_groups.len()
+    );
+}
+
+/// Assert that the resolver was asked for `pubkey` on some iteration.
+///
+/// Checks [`ResolverResult::missing_per_iteration`], which holds accounts after
+/// placeholder substitution, so pass the real payer or guardian set address
+/// rather than a placeholder.
+///
+/// # Panics
+///
+/// Panics with the accounts requested on each iteration if `pubkey` is not
+/// among them.
+pub fn assert_requested(result: &ResolverResult, pubkey: &Pubkey) {
+    assert!(
+        result
+            .missing_per_iteration
+            .iter()
+            .any(|requested| requested.contains(pubkey)),
+        "resolver never requested {}; requested per iteration: {:?}",
+        pubkey,
+        result.missing_per_iteration
+    );
+}
+
 /// Submit a signed VAA to a program via the resolver-executor flow, with full
 /// safety checks (negative test + optional replay protection).
 ///
@@ -133,3 +283,62 @@ pub fn broadcast_vaa(
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hooked_connection_skips_first_iteration() {
+        let mut svm = LiteSVM::new();
+        let mut calls = 0;
+
+        {
+            let conn = HookedConnection::new(&mut svm, |_: &mut LiteSVM| calls += 1);
+            for _ in 0..3 {
+                conn.get_latest_blockhash().unwrap();
+            }
+        }
+
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_build_resolver_instruction_data() {
+        let data = build_resolver_instruction_data(&[1, 2, 3]);
+
+        assert_eq!(
+            &data[..8],
+            &wormhole_svm_submit::resolve::RESOLVER_EXECUTE_VAA_V1
+        );
+        assert_eq!(&data[8..12], &3u32.to_le_bytes());
+        assert_eq!(&data[12..], &[1, 2, 3]);
+    }
+
+    #[test]
+    fn test_assert_resolved_in() {
+        let result = ResolverResult::new(vec![], 2, vec![vec![]]);
+        assert_resolved_in(&result, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "resolver resolved in 3 iteration(s), expected 2")]
+    fn test_assert_resolved_in_mismatch() {
+        let result = ResolverResult::new(vec![], 3, vec![vec![], vec![]]);
+        assert_resolved_in(&result, 2);
+    }
+
+    #[test]
+    fn test_assert_requested() {
+        let config = Pubkey::new_unique();
+        let result = ResolverResult::new(vec![], 3, vec![vec![Pubkey::new_unique()], vec![config]]);
+        assert_requested(&result, &config);
+    }
+
+    #[test]
+    #[should_panic(expected = "resolver never requested")]
+    fn test_assert_requested_missing() {
+        let result = ResolverResult::new(vec![], 2, vec![vec![Pubkey::new_unique()]]);
+        assert_requested(&result, &Pubkey::new_unique());
+    }
+}
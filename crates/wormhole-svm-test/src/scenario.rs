@@ -0,0 +1,112 @@
+//! Multi-emitter VAA streams with per-emitter sequence tracking.
+
+use crate::TestVaa;
+
+/// A set of emitters that produce VAAs with correctly incrementing sequences.
+///
+/// Each emitter keeps its own sequence counter, starting at 0, as the Core
+/// Bridge does. Use it to model the interleaved stream a relayer sees.
+///
+/// # Example
+///
+/// ```rust
+/// use wormhole_svm_test::TestScenario;
+///
+/// let mut scenario = TestScenario::new();
+/// let solana = scenario.add_emitter(1, [0xAA; 32]);
+/// let ethereum = scenario.add_emitter(2, [0xBB; 32]);
+///
+/// assert_eq!(scenario.next_vaa(solana, vec![1]).sequence, 0);
+/// assert_eq!(scenario.next_vaa(ethereum, vec![2]).sequence, 0);
+/// assert_eq!(scenario.next_vaa(solana, vec![3]).sequence, 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TestScenario {
+    emitters: Vec<ScenarioEmitter>,
+}
+
+#[derive(Clone, Debug)]
+struct ScenarioEmitter {
+    chain: u16,
+    address: [u8; 32],
+    next_sequence: u64,
+}
+
+impl TestScenario {
+    /// Create a scenario with no emitters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register an emitter and return its id for [`next_vaa`](Self::next_vaa).
+    pub fn add_emitter(&mut self, chain: u16, address: [u8; 32]) -> usize {
+        self.emitters.push(ScenarioEmitter {
+            chain,
+            address,
+            next_sequence: 0,
+        });
+        self.emitters.len() - 1
+    }
+
+    /// Build the next VAA from `emitter_id` and advance its sequence.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `emitter_id` was not returned by [`add_emitter`](Self::add_emitter).
+    pub fn next_vaa(&mut self, emitter_id: usize, payload: Vec<u8>) -> TestVaa {
+        let emitter = self.emitter_mut(emitter_id);
+        let sequence = emitter.next_sequence;
+        emitter.next_sequence += 1;
+
+        TestVaa::new(emitter.chain, emitter.address, sequence, payload)
+    }
+
+    /// The sequence the next VAA from `emitter_id` will carry.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `emitter_id` was not returned by [`add_emitter`](Self::add_emitter).
+    pub fn next_sequence(&self, emitter_id: usize) -> u64 {
+        self.emitters
+            .get(emitter_id)
+            .unwrap_or_else(|| panic!("unknown emitter id {}", emitter_id))
+            .next_sequence
+    }
+
+    fn emitter_mut(&mut self, emitter_id: usize) -> &mut ScenarioEmitter {
+        self.emitters
+            .get_mut(emitter_id)
+            .unwrap_or_else(|| panic!("unknown emitter id {}", emitter_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sequences_are_per_emitter() {
+        let mut scenario = TestScenario::new();
+        let a = scenario.add_emitter(1, [0xAA; 32]);
+        let b = scenario.add_emitter(2, [0xBB; 32]);
+
+        let first = scenario.next_vaa(a, vec![1]);
+        scenario.next_vaa(a, vec![2]);
+        let other = scenario.next_vaa(b, vec![3]);
+
+        assert_eq!((first.emitter_chain, first.sequence), (1, 0));
+        assert_eq!(
+            (other.emitter_chain, other.emitter_address),
+            (2, [0xBB; 32])
+        );
+        assert_eq!(other.sequence, 0);
+        assert_eq!(scenario.next_sequence(a), 2);
+        assert_eq!(scenario.next_sequence(b), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "unknown emitter id 0")]
+    fn test_unknown_emitter() {
+        TestScenario::new().next_vaa(0, vec![]);
+    }
+}
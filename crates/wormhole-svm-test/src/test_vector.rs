@@ -0,0 +1,44 @@
+//! Language-neutral test vectors for cross-implementation conformance tests.
+
+use serde::{Deserialize, Serialize};
+
+/// A signed VAA and everything needed to check its verification elsewhere.
+///
+/// All byte fields are lowercase hex without a `0x` prefix so the vector can
+/// be consumed from any language. Build one with
+/// [`TestVaa::to_test_vector`](crate::TestVaa::to_test_vector).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// The full signed VAA.
+    pub vaa: String,
+    /// The VAA body (the signed portion).
+    pub body: String,
+    /// The double-keccak256 digest of the body.
+    pub digest: String,
+    /// The guardian set index from the VAA header.
+    pub guardian_set_index: u32,
+    /// Ethereum addresses of the guardian set, in index order.
+    pub guardian_addresses: Vec<String>,
+    /// The 66-byte guardian signatures (`[index, r, s, v]`).
+    pub signatures: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{TestGuardianSet, TestVaa};
+
+    #[test]
+    fn test_to_test_vector() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3, 4]);
+
+        let vector = vaa.to_test_vector(&guardians);
+
+        assert_eq!(vector.vaa, hex::encode(vaa.sign(&guardians)));
+        assert!(vector.vaa.ends_with(&vector.body));
+        assert_eq!(vector.digest.len(), 64);
+        assert_eq!(vector.guardian_addresses.len(), 3);
+        assert_eq!(vector.signatures.len(), 3);
+        assert!(vector.signatures.iter().all(|s| s.len() == 132));
+    }
+}
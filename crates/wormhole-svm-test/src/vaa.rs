@@ -1,6 +1,7 @@
 //! VAA construction utilities for testing.
 
 use sha3::{Digest, Keccak256};
+use thiserror::Error;
 
 use crate::TestGuardianSet;
 
@@ -47,6 +48,131 @@ impl Default for VaaChecks {
     }
 }
 
+/// The only VAA version currently accepted on-chain.
+pub const VAA_VERSION: u8 = 1;
+
+/// Byte offset of the 4-byte big-endian guardian set index in a signed VAA.
+///
+/// Header layout: `version (1) | guardian_set_index (4) | num_signatures (1) | signatures (66 each)`.
+pub const VAA_GUARDIAN_SET_INDEX_OFFSET: usize = 1;
+
+/// Boundary values for the 8-byte VAA sequence, for [`TestVaa::with_sequence_edge`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SequenceEdge {
+    /// `0`: the first message from an emitter; breaks code that treats 0 as "unset".
+    Zero,
+    /// `u32::MAX`: the largest value that survives truncation to 32 bits.
+    U32Max,
+    /// `u32::MAX + 1`: wraps to 0 when stored as a `u32`.
+    U32Overflow,
+    /// `i64::MAX`: the largest value that fits a signed 64-bit column.
+    I64Max,
+    /// `i64::MAX + 1`: negative when reinterpreted as an `i64`.
+    I64Overflow,
+    /// `u64::MAX`: `sequence + 1` overflows.
+    Max,
+}
+
+impl SequenceEdge {
+    /// Every edge, in ascending order of value.
+    pub const ALL: [SequenceEdge; 6] = [
+        SequenceEdge::Zero,
+        SequenceEdge::U32Max,
+        SequenceEdge::U32Overflow,
+        SequenceEdge::I64Max,
+        SequenceEdge::I64Overflow,
+        SequenceEdge::Max,
+    ];
+
+    /// The sequence number for this edge.
+    pub fn value(&self) -> u64 {
+        match self {
+            SequenceEdge::Zero => 0,
+            SequenceEdge::U32Max => u32::MAX as u64,
+            SequenceEdge::U32Overflow => u32::MAX as u64 + 1,
+            SequenceEdge::I64Max => i64::MAX as u64,
+            SequenceEdge::I64Overflow => i64::MAX as u64 + 1,
+            SequenceEdge::Max => u64::MAX,
+        }
+    }
+}
+
+/// A guardian set index that no helper in this crate ever installs.
+///
+/// Used by [`TestVaa::with_unknown_set_index`] callers that just need an
+/// index whose guardian set PDA is guaranteed to be absent.
+pub const UNKNOWN_GUARDIAN_SET_INDEX: u32 = u32::MAX;
+
+/// A guardian set index near `u32::MAX`, from [`TestVaa::with_large_set_index`].
+///
+/// Every byte is distinct and non-zero, so truncating it to a `u8` or `u16`,
+/// or swapping its byte order, gives a visibly different index.
+pub const LARGE_GUARDIAN_SET_INDEX: u32 = 0xFEDC_BA98;
+
+/// Maximum number of nonces [`TestVaa::mine_digest_prefix`] tries.
+pub const MAX_DIGEST_MINING_ITERATIONS: u32 = 1 << 20;
+
+/// Reasons a [`TestVaa`] constructor or modifier can fail.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum TestVaaError {
+    #[error(
+        "no nonce in {iterations} tries gives a digest starting with 0x{}",
+        hex::encode(.prefix)
+    )]
+    DigestPrefixNotFound { prefix: Vec<u8>, iterations: u32 },
+    #[error(
+        "chain {chain} uses {format:?} emitter addresses, but 0x{} does not match (an EVM \
+         address needs 12 leading zero bytes; a native 32-byte address should not have them)",
+        hex::encode(.emitter_address)
+    )]
+    EmitterFormatMismatch {
+        chain: u16,
+        format: EmitterFormat,
+        emitter_address: [u8; 32],
+    },
+}
+
+/// Length of the fixed VAA body fields preceding the payload.
+///
+/// `timestamp (4) | nonce (4) | emitter_chain (2) | emitter_address (32) | sequence (8) | consistency_level (1)`.
+pub const VAA_BODY_HEADER_LEN: usize = 51;
+
+/// Largest payload whose VAA body still fits in a single transaction as the
+/// instruction data of a typical verify instruction.
+///
+/// Derived for a legacy transaction with one signer (the fee payer), five
+/// account keys (payer, guardian set, guardian signatures, Verify VAA Shim and
+/// the consumer program), and one instruction over four accounts whose data is
+/// `[discriminator (1)][bump (1)][body length (4)][body]`, the framing used by
+/// the example verifier:
+///
+/// ```text
+///   1232  packet data size
+/// -   65  signature count + one signature
+/// -    3  message header
+/// -  161  account keys (compact length + 5 x 32)
+/// -   32  recent blockhash
+/// -    9  instruction count, program index, account indices (1 + 4), data length (2)
+/// -    6  instruction framing (discriminator, bump, u32 length)
+/// -   51  body header (VAA_BODY_HEADER_LEN)
+/// =  905
+/// ```
+///
+/// Programs with more accounts, more signers or an 8-byte Anchor
+/// discriminator have correspondingly less room.
+pub const MAX_SINGLE_TX_PAYLOAD_LEN: usize = 905;
+
+/// A serialized VAA together with the signatures it carries, from
+/// [`TestVaa::sign_full`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignedVaa {
+    /// The full signed VAA.
+    pub vaa_bytes: Vec<u8>,
+    /// The 66-byte guardian signatures embedded in `vaa_bytes`, ready for
+    /// `post_signatures`.
+    pub guardian_signatures: Vec<[u8; 66]>,
+}
+
 /// A test VAA for construction and signing.
 #[derive(Clone)]
 pub struct TestVaa {
@@ -64,7 +190,16 @@ pub struct TestVaa {
     pub nonce: u32,
     /// The consistency level (defaults to 1 = Confirmed).
     pub consistency_level: u8,
-    /// The guardian set index (defaults to 0).
+    /// The version byte written to the VAA header (defaults to [`VAA_VERSION`]).
+    ///
+    /// Like the guardian set index, it is not part of the signed body.
+    pub version: u8,
+    /// The guardian set index written to the VAA header (defaults to 0).
+    ///
+    /// Occupies bytes `1..5` of the signed VAA (big-endian, see
+    /// [`VAA_GUARDIAN_SET_INDEX_OFFSET`]). It is *not* part of the signed body,
+    /// so it has no effect on the digest or the signatures: which guardians
+    /// sign is decided solely by the [`TestGuardianSet`] passed to `sign`.
     pub guardian_set_index: u32,
     /// Which automatic negative tests to run in `with_vaa`.
     pub checks: VaaChecks,
@@ -86,12 +221,248 @@ impl TestVaa {
             timestamp: 1234567890,
             nonce: 0,
             consistency_level: 1,
+            version: VAA_VERSION,
             guardian_set_index: 0,
             checks: VaaChecks::default(),
         }
     }
 
+    /// Start building a VAA field by field.
+    ///
+    /// Every field not set on the builder keeps the [`minimal`](Self::minimal)
+    /// value, which uses the [`new`](Self::new) defaults for the timestamp,
+    /// nonce, consistency level, version and guardian set index.
+    ///
+    /// ```rust
+    /// use wormhole_svm_test::TestVaa;
+    ///
+    /// let vaa = TestVaa::builder()
+    ///     .emitter_chain(2)
+    ///     .sequence(7)
+    ///     .nonce(99)
+    ///     .consistency_level(200)
+    ///     .build();
+    /// assert_eq!(vaa.nonce, 99);
+    /// assert_eq!(vaa.timestamp, TestVaa::minimal().timestamp);
+    /// ```
+    pub fn builder() -> TestVaaBuilder {
+        TestVaaBuilder {
+            vaa: Self::minimal(),
+        }
+    }
+
+    /// Set the header version byte.
+    ///
+    /// Only [`VAA_VERSION`] (1) is valid on-chain; any other version still
+    /// signs and serializes, so consumers can be checked to reject unknown
+    /// versions rather than misparse them.
+    ///
+    /// ```rust
+    /// use wormhole_svm_test::{TestGuardianSet, TestVaa};
+    ///
+    /// let guardians = TestGuardianSet::generate(1, 1);
+    /// let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]).with_version(2);
+    /// assert_eq!(vaa.sign(&guardians)[0], 2);
+    /// ```
+    pub fn with_version(mut self, version: u8) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Like [`new`](Self::new), but rejects an emitter address whose format
+    /// does not match the chain's [`emitter_format`].
+    ///
+    /// - EVM chains require 12 leading zero bytes (a padded 20-byte address).
+    /// - Solana, Sui and Aptos reject 12 leading zero bytes, which almost
+    ///   always means an EVM address was padded by mistake. The governance
+    ///   emitter on the governance chain is the one exception.
+    ///
+    /// Chains outside the built-in table are accepted as-is. Use `new` for
+    /// intentionally mismatched fixtures.
+    pub fn new_validated(
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        payload: Vec<u8>,
+    ) -> Result<Self, TestVaaError> {
+        let padded = emitter_address[..12].iter().all(|&b| b == 0);
+        let governance = emitter_chain == crate::payloads::GOVERNANCE_CHAIN
+            && emitter_address == crate::payloads::GOVERNANCE_EMITTER;
+
+        let Some(format) = emitter_format(emitter_chain) else {
+            return Ok(Self::new(emitter_chain, emitter_address, sequence, payload));
+        };
+        let matches = match format {
+            EmitterFormat::Evm20 => padded,
+            EmitterFormat::Native32 => !padded || governance,
+        };
+        if !matches {
+            return Err(TestVaaError::EmitterFormatMismatch {
+                chain: emitter_chain,
+                format,
+                emitter_address,
+            });
+        }
+
+        Ok(Self::new(emitter_chain, emitter_address, sequence, payload))
+    }
+
+    /// Replace the sequence with a boundary value.
+    ///
+    /// ```rust
+    /// use wormhole_svm_test::{SequenceEdge, TestVaa};
+    ///
+    /// let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]).with_sequence_edge(SequenceEdge::Max);
+    /// assert_eq!(vaa.sequence, u64::MAX);
+    /// ```
+    pub fn with_sequence_edge(mut self, edge: SequenceEdge) -> Self {
+        self.sequence = edge.value();
+        self
+    }
+
+    /// Create a contract upgrade governance VAA for `module` on `target_chain`.
+    ///
+    /// Emitted by [`GOVERNANCE_EMITTER`](crate::payloads::GOVERNANCE_EMITTER)
+    /// on [`GOVERNANCE_CHAIN`](crate::payloads::GOVERNANCE_CHAIN) at sequence
+    /// 0, with the payload from
+    /// [`payloads::contract_upgrade`](crate::payloads::contract_upgrade): action
+    /// 1 for `Core`, action 2 for the other modules.
+    pub fn contract_upgrade(
+        module: [u8; 32],
+        target_chain: u16,
+        new_implementation: [u8; 32],
+    ) -> Self {
+        Self::new(
+            crate::payloads::GOVERNANCE_CHAIN,
+            crate::payloads::GOVERNANCE_EMITTER,
+            0,
+            crate::payloads::contract_upgrade(module, target_chain, new_implementation),
+        )
+    }
+
+    /// Set the timestamp to `seconds_ago` seconds before `now`, for testing
+    /// consumers that reject stale VAAs.
+    ///
+    /// `now` is the on-chain clock's Unix time, e.g.
+    /// `svm.get_sysvar::<Clock>().unix_timestamp as u32`. Saturates at 0.
+    pub fn expired(mut self, now: u32, seconds_ago: u32) -> Self {
+        self.timestamp = now.saturating_sub(seconds_ago);
+        self
+    }
+
+    /// Set the timestamp to `seconds_ahead` seconds after `now`, for testing
+    /// consumers that reject VAAs from the future.
+    ///
+    /// `now` is the on-chain clock's Unix time, as for
+    /// [`expired`](Self::expired). Saturates at `u32::MAX`.
+    pub fn future(mut self, now: u32, seconds_ahead: u32) -> Self {
+        self.timestamp = now.saturating_add(seconds_ahead);
+        self
+    }
+
+    /// Create the smallest valid VAA: an empty (zero-byte) payload, so the body
+    /// is exactly [`VAA_BODY_HEADER_LEN`] bytes.
+    ///
+    /// The emitter is chain 1 with an all-zero address at sequence 0, and the
+    /// rest of the header uses the [`new`](Self::new) defaults. It signs and
+    /// verifies like any other VAA; use it to catch off-by-one bugs in
+    /// consumers' handling of empty payloads.
+    pub fn minimal() -> Self {
+        Self::new(1, [0; 32], 0, Vec::new())
+    }
+
+    /// Create the VAA for a [`KnownVector`](crate::known_vectors::KnownVector).
+    ///
+    /// Signed with [`TestGuardianSet::default`] (the devnet guardian), it
+    /// equals [`KnownVector::expected_bytes`](crate::known_vectors::KnownVector::expected_bytes)
+    /// byte for byte.
+    pub fn known_vector(vector: crate::known_vectors::KnownVector) -> Self {
+        vector.vaa()
+    }
+
+    /// Create a VAA whose payload is exactly [`MAX_SINGLE_TX_PAYLOAD_LEN`] bytes.
+    ///
+    /// The payload is a repeating `0x00..=0xFF` pattern so truncation or
+    /// off-by-one copies are visible in the stored bytes.
+    pub fn with_max_payload(emitter_chain: u16, emitter_address: [u8; 32], sequence: u64) -> Self {
+        Self::with_payload_len(
+            emitter_chain,
+            emitter_address,
+            sequence,
+            MAX_SINGLE_TX_PAYLOAD_LEN,
+        )
+    }
+
+    /// Create a VAA whose payload is one byte longer than
+    /// [`MAX_SINGLE_TX_PAYLOAD_LEN`], so its verify transaction does not fit.
+    pub fn with_oversized_payload(
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+    ) -> Self {
+        Self::with_payload_len(
+            emitter_chain,
+            emitter_address,
+            sequence,
+            MAX_SINGLE_TX_PAYLOAD_LEN + 1,
+        )
+    }
+
+    fn with_payload_len(
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        len: usize,
+    ) -> Self {
+        let payload = (0..len).map(|i| i as u8).collect();
+        Self::new(emitter_chain, emitter_address, sequence, payload)
+    }
+
+    /// Create a VAA whose payload is `unit` repeated `repeats` times.
+    ///
+    /// The payload is `unit.len() * repeats` bytes and the body
+    /// [`VAA_BODY_HEADER_LEN`] more, which makes precise sizes easy to hit for
+    /// compute-unit and allocation stress tests.
+    pub fn with_repeated_payload(
+        emitter_chain: u16,
+        emitter_address: [u8; 32],
+        sequence: u64,
+        unit: &[u8],
+        repeats: usize,
+    ) -> Self {
+        Self::new(
+            emitter_chain,
+            emitter_address,
+            sequence,
+            unit.repeat(repeats),
+        )
+    }
+
+    /// Build one VAA per emitter chain, identical apart from `emitter_chain`.
+    ///
+    /// Returns `(chain, vaa)` pairs in the order of `chains`, for parameterized
+    /// tests of programs that route on the emitter chain.
+    pub fn across_chains(
+        chains: &[u16],
+        emitter_address: [u8; 32],
+        sequence: u64,
+        payload: Vec<u8>,
+    ) -> Vec<(u16, TestVaa)> {
+        chains
+            .iter()
+            .map(|&chain| {
+                (
+                    chain,
+                    Self::new(chain, emitter_address, sequence, payload.clone()),
+                )
+            })
+            .collect()
+    }
+
     /// Build the VAA body bytes (without version, guardian set index, or signatures).
+    ///
+    /// These are the pre-hash bytes guardians sign over.
+    #[doc(alias = "body_bytes")]
     pub fn body(&self) -> Vec<u8> {
         let mut body = Vec::new();
 
@@ -119,11 +490,48 @@ impl TestVaa {
         body
     }
 
+    /// Length in bytes of this VAA once signed with `signature_count` signatures.
+    ///
+    /// `6` header bytes, `66` per signature, then the body
+    /// ([`VAA_BODY_HEADER_LEN`] plus the payload). Computed without signing.
+    pub fn serialized_len(&self, signature_count: usize) -> usize {
+        6 + 66 * signature_count + VAA_BODY_HEADER_LEN + self.payload.len()
+    }
+
+    /// Assert that the payload equals `expected`.
+    ///
+    /// Returns `self` so it can be chained in builder-style test code.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the first differing offset and both payloads in hex.
+    pub fn expect_payload(&self, expected: &[u8]) -> &Self {
+        if self.payload != expected {
+            let offset = self
+                .payload
+                .iter()
+                .zip(expected)
+                .position(|(a, e)| a != e)
+                .unwrap_or(self.payload.len().min(expected.len()));
+            panic!(
+                "payload differs at byte {} ({} vs {} bytes)\n  actual:   {}\n  expected: {}",
+                offset,
+                self.payload.len(),
+                expected.len(),
+                hex::encode(&self.payload),
+                hex::encode(expected)
+            );
+        }
+        self
+    }
+
     /// Compute the VAA digest (double keccak256 of body).
+    ///
+    /// This is what guardians sign, and the value to pass to the Verify VAA
+    /// Shim's `verify_hash` from a custom verify instruction.
+    #[doc(alias = "body_digest")]
     pub fn digest(&self) -> [u8; 32] {
-        let body = self.body();
-        let message_hash = Keccak256::digest(&body);
-        Keccak256::digest(message_hash).into()
+        vaa_body_digest(&self.body())
     }
 
     /// Build a signed VAA with all guardians in the set.
@@ -133,6 +541,21 @@ impl TestVaa {
         self.build_signed_vaa(&body, &signatures)
     }
 
+    /// Sign with all guardians in the set, returning both the serialized VAA
+    /// and its signatures.
+    ///
+    /// Equivalent to calling [`sign`](Self::sign) and
+    /// [`guardian_signatures`](Self::guardian_signatures), but both outputs
+    /// come from a single signing pass so they cannot drift apart.
+    pub fn sign_full(&self, guardians: &TestGuardianSet) -> SignedVaa {
+        let body = self.body();
+        let guardian_signatures = guardians.sign_vaa_body(&body);
+        SignedVaa {
+            vaa_bytes: self.build_signed_vaa(&body, &guardian_signatures),
+            guardian_signatures,
+        }
+    }
+
     /// Build a signed VAA with specific guardians (by index).
     pub fn sign_with(&self, guardians: &TestGuardianSet, indices: &[u8]) -> Vec<u8> {
         let body = self.body();
@@ -140,37 +563,389 @@ impl TestVaa {
         self.build_signed_vaa(&body, &signatures)
     }
 
+    /// Assemble a signed VAA from signature batches collected from several
+    /// signing parties.
+    ///
+    /// The batches are merged, sorted by guardian index and deduplicated on
+    /// index: when batches overlap, the signature from the earliest batch is
+    /// kept. Signatures are not checked against the body, so a batch signed
+    /// over a different body still assembles (and then fails verification).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a signature's guardian index is not below `guardian_count`.
+    pub fn assemble_from_partials(
+        &self,
+        guardian_count: u8,
+        partial_batches: &[Vec<[u8; 66]>],
+    ) -> Vec<u8> {
+        self.assemble(guardian_count, partial_batches, true)
+    }
+
+    /// Like [`assemble_from_partials`](Self::assemble_from_partials), but keeps
+    /// the signatures in the order the batches list them.
+    ///
+    /// Overlaps are still deduplicated. The Verify VAA Shim requires strictly
+    /// increasing guardian indices, so use this only to build deliberately
+    /// unsorted VAAs for tests that check they are rejected.
+    pub fn assemble_from_partials_unsorted(
+        &self,
+        guardian_count: u8,
+        partial_batches: &[Vec<[u8; 66]>],
+    ) -> Vec<u8> {
+        self.assemble(guardian_count, partial_batches, false)
+    }
+
+    fn assemble(
+        &self,
+        guardian_count: u8,
+        partial_batches: &[Vec<[u8; 66]>],
+        sort: bool,
+    ) -> Vec<u8> {
+        let mut signatures: Vec<[u8; 66]> = Vec::new();
+        for sig in partial_batches.iter().flatten() {
+            assert!(
+                sig[0] < guardian_count,
+                "guardian index {} is out of range for a set of {} guardian(s)",
+                sig[0],
+                guardian_count
+            );
+            if !signatures.iter().any(|s| s[0] == sig[0]) {
+                signatures.push(*sig);
+            }
+        }
+        if sort {
+            signatures.sort_by_key(|sig| sig[0]);
+        }
+
+        self.build_signed_vaa(&self.body(), &signatures)
+    }
+
+    /// Build a signed VAA whose header carries `header_guardian_set_index`
+    /// instead of [`guardian_set_index`](Self::guardian_set_index).
+    ///
+    /// The signatures are identical to [`sign`](Self::sign) with the same
+    /// guardians; only bytes `1..5` of the header differ. Use this to build a
+    /// VAA whose header index disagrees with the set that actually signed it.
+    pub fn sign_with_header_index(
+        &self,
+        guardians: &TestGuardianSet,
+        header_guardian_set_index: u32,
+    ) -> Vec<u8> {
+        let vaa = Self {
+            guardian_set_index: header_guardian_set_index,
+            ..self.clone()
+        };
+        vaa.sign(guardians)
+    }
+
     /// Get guardian signatures for use with post_signatures instruction.
     pub fn guardian_signatures(&self, guardians: &TestGuardianSet) -> Vec<[u8; 66]> {
         let body = self.body();
         guardians.sign_vaa_body(&body)
     }
 
-    /// Build the full signed VAA bytes.
-    fn build_signed_vaa(&self, body: &[u8], signatures: &[[u8; 66]]) -> Vec<u8> {
-        let mut vaa = Vec::new();
+    /// Copy this VAA with its header pointing at a guardian set that is not installed.
+    ///
+    /// `index` should be one with no guardian set account, such as
+    /// [`UNKNOWN_GUARDIAN_SET_INDEX`]; its PDA then points at an absent account
+    /// and verification fails because the set cannot be found. This differs from
+    /// a *mismatched* index (see
+    /// [`negative_fixtures::wrong_index_vaa`](crate::negative_fixtures::wrong_index_vaa)),
+    /// where a set does exist at the index but holds different keys.
+    ///
+    /// Only the header changes; signing with any set still works, since the
+    /// index is not part of the signed body.
+    pub fn with_unknown_set_index(&self, index: u32) -> Self {
+        Self {
+            guardian_set_index: index,
+            ..self.clone()
+        }
+    }
 
-        // Version (1 byte)
-        vaa.push(1);
+    /// Copy this VAA with its header carrying [`LARGE_GUARDIAN_SET_INDEX`].
+    ///
+    /// Guardian indices within a set are `u8`, but the set index is a full
+    /// `u32`; this catches programs that store or derive from it with too
+    /// small an integer. Install the signing set at the same index (e.g.
+    /// `setup_wormhole(.., LARGE_GUARDIAN_SET_INDEX, ..)`) for it to verify.
+    pub fn with_large_set_index(&self) -> Self {
+        Self {
+            guardian_set_index: LARGE_GUARDIAN_SET_INDEX,
+            ..self.clone()
+        }
+    }
 
-        // Guardian set index (4 bytes, big-endian)
-        vaa.extend_from_slice(&self.guardian_set_index.to_be_bytes());
+    /// Find a nonce for which [`digest`](Self::digest) starts with `prefix`.
+    ///
+    /// Tries nonces counting up from `self.nonce` (wrapping) and returns a copy
+    /// of this VAA with the first matching nonce. Each prefix byte multiplies
+    /// the expected work by 256: one byte takes ~256 tries, two bytes ~65,536.
+    /// Gives up after [`MAX_DIGEST_MINING_ITERATIONS`] with
+    /// [`TestVaaError::DigestPrefixNotFound`], so prefixes of three or more
+    /// bytes will usually fail.
+    pub fn mine_digest_prefix(&self, prefix: &[u8]) -> Result<Self, TestVaaError> {
+        let mut vaa = self.clone();
+        for _ in 0..MAX_DIGEST_MINING_ITERATIONS {
+            if vaa.digest().starts_with(prefix) {
+                return Ok(vaa);
+            }
+            vaa.nonce = vaa.nonce.wrapping_add(1);
+        }
+
+        Err(TestVaaError::DigestPrefixNotFound {
+            prefix: prefix.to_vec(),
+            iterations: MAX_DIGEST_MINING_ITERATIONS,
+        })
+    }
 
-        // Number of signatures (1 byte)
-        vaa.push(signatures.len() as u8);
+    /// Serialize the signature section of the VAA signed by `guardians`.
+    ///
+    /// Returns `num_signatures (1) | signatures (66 each)` exactly as it appears
+    /// in [`sign`](Self::sign)'s output, starting at byte 5 (after the version
+    /// and guardian set index). Each entry is `[guardian_index, r (32), s (32), v]`.
+    ///
+    /// The entries are the same 66-byte values [`guardian_signatures`](Self::guardian_signatures)
+    /// returns for `post_signatures`, but the framing differs: the Verify VAA
+    /// Shim's posted signatures account stores them as a borsh `Vec` (4-byte
+    /// little-endian count) behind its discriminator, refund recipient and guardian
+    /// set index, whereas a VAA uses a single count byte.
+    pub fn signature_section_bytes(&self, guardians: &TestGuardianSet) -> Vec<u8> {
+        signature_section(&self.guardian_signatures(guardians))
+    }
+
+    /// Guardian signatures from exactly [`quorum`](TestGuardianSet::quorum) guardians (the first ones by index).
+    ///
+    /// The smallest batch that verifies; pair with
+    /// [`sign_below_quorum`](Self::sign_below_quorum) to pin the threshold.
+    pub fn sign_with_quorum(&self, guardians: &TestGuardianSet) -> Vec<[u8; 66]> {
+        guardians.sign_vaa_body_quorum(&self.body())
+    }
+
+    /// Guardian signatures from one fewer than [`quorum`](TestGuardianSet::quorum) guardians.
+    ///
+    /// The largest batch that must be rejected for lack of quorum. For a
+    /// single-guardian set this is empty, which the Verify VAA Shim already
+    /// rejects when posting.
+    pub fn sign_below_quorum(&self, guardians: &TestGuardianSet) -> Vec<[u8; 66]> {
+        self.sign_first(guardians, guardians.quorum() - 1)
+    }
+
+    fn sign_first(&self, guardians: &TestGuardianSet, count: usize) -> Vec<[u8; 66]> {
+        let body = self.body();
+        guardians
+            .iter()
+            .take(count)
+            .map(|g| g.sign_vaa_body(&body))
+            .collect()
+    }
+
+    /// Check offline that this VAA, signed by `guardians`, would verify on-chain.
+    ///
+    /// Runs the same quorum, ordering, membership and recovery checks as the
+    /// Verify VAA Shim (see [`verify_signatures`](crate::verify_signatures))
+    /// against `guardians.eth_addresses()`, and returns a descriptive error
+    /// for the first check that fails.
+    pub fn preflight(&self, guardians: &TestGuardianSet) -> Result<(), String> {
+        self.verify_offline(guardians).map_err(|e| {
+            format!(
+                "VAA (emitter chain {}, sequence {}) would not verify against guardian set {}: {}",
+                self.emitter_chain, self.sequence, self.guardian_set_index, e
+            )
+        })
+    }
 
-        // Signatures (66 bytes each)
-        for sig in signatures {
-            vaa.extend_from_slice(sig);
+    /// The check behind [`preflight`](Self::preflight), with the bare
+    /// [`VerifyError`](crate::VerifyError).
+    pub(crate) fn verify_offline(
+        &self,
+        guardians: &TestGuardianSet,
+    ) -> Result<(), crate::VerifyError> {
+        let signatures = self.guardian_signatures(guardians);
+        crate::verify_signatures(&self.digest(), &signatures, &guardians.eth_addresses())
+    }
+
+    /// Sign this VAA with `guardians` and capture it as a [`TestVector`](crate::TestVector).
+    #[cfg(feature = "serde")]
+    pub fn to_test_vector(&self, guardians: &TestGuardianSet) -> crate::TestVector {
+        crate::TestVector {
+            vaa: hex::encode(self.sign(guardians)),
+            body: hex::encode(self.body()),
+            digest: hex::encode(self.digest()),
+            guardian_set_index: self.guardian_set_index,
+            guardian_addresses: guardians.eth_addresses().iter().map(hex::encode).collect(),
+            signatures: self
+                .guardian_signatures(guardians)
+                .iter()
+                .map(hex::encode)
+                .collect(),
         }
+    }
 
-        // Body
+    /// Build the full signed VAA bytes.
+    fn build_signed_vaa(&self, body: &[u8], signatures: &[[u8; 66]]) -> Vec<u8> {
+        let mut vaa = vaa_header_bytes(self.version, self.guardian_set_index, signatures);
         vaa.extend_from_slice(body);
-
         vaa
     }
 }
 
+/// Builder for [`TestVaa`], from [`TestVaa::builder`].
+#[derive(Clone)]
+pub struct TestVaaBuilder {
+    vaa: TestVaa,
+}
+
+impl TestVaaBuilder {
+    /// Set the emitter chain ID.
+    pub fn emitter_chain(mut self, emitter_chain: u16) -> Self {
+        self.vaa.emitter_chain = emitter_chain;
+        self
+    }
+
+    /// Set the 32-byte emitter address.
+    pub fn emitter_address(mut self, emitter_address: [u8; 32]) -> Self {
+        self.vaa.emitter_address = emitter_address;
+        self
+    }
+
+    /// Set the sequence number.
+    pub fn sequence(mut self, sequence: u64) -> Self {
+        self.vaa.sequence = sequence;
+        self
+    }
+
+    /// Set the payload.
+    pub fn payload(mut self, payload: Vec<u8>) -> Self {
+        self.vaa.payload = payload;
+        self
+    }
+
+    /// Set the body timestamp.
+    pub fn timestamp(mut self, timestamp: u32) -> Self {
+        self.vaa.timestamp = timestamp;
+        self
+    }
+
+    /// Set the body nonce.
+    pub fn nonce(mut self, nonce: u32) -> Self {
+        self.vaa.nonce = nonce;
+        self
+    }
+
+    /// Set the body consistency level.
+    pub fn consistency_level(mut self, consistency_level: u8) -> Self {
+        self.vaa.consistency_level = consistency_level;
+        self
+    }
+
+    /// Set the header version byte (see [`TestVaa::with_version`]).
+    pub fn version(mut self, version: u8) -> Self {
+        self.vaa.version = version;
+        self
+    }
+
+    /// Set the header guardian set index.
+    pub fn guardian_set_index(mut self, guardian_set_index: u32) -> Self {
+        self.vaa.guardian_set_index = guardian_set_index;
+        self
+    }
+
+    /// Finish building.
+    pub fn build(self) -> TestVaa {
+        self.vaa
+    }
+}
+
+/// Assemble the header of a signed VAA, everything before the body.
+///
+/// Fields, in order:
+///
+/// ```text
+/// version (1) | guardian_set_index (4, big-endian) | num_signatures (1) | signatures (66 each)
+/// ```
+///
+/// Each signature is `guardian_index (1) | r (32) | s (32) | v (1)`. Append
+/// [`TestVaa::body`] to get a full VAA; pass a different version or a
+/// mismatched index to build malformed-header fixtures. The signature count
+/// is truncated to a `u8`, so more than 255 signatures yields a header whose
+/// count disagrees with its length.
+pub fn vaa_header_bytes(version: u8, guardian_set_index: u32, signatures: &[[u8; 66]]) -> Vec<u8> {
+    let mut header = Vec::with_capacity(6 + 66 * signatures.len());
+    header.push(version);
+    header.extend_from_slice(&guardian_set_index.to_be_bytes());
+    header.extend_from_slice(&signature_section(signatures));
+    header
+}
+
+/// Serialize `num_signatures (1) | signatures (66 each)`.
+fn signature_section(signatures: &[[u8; 66]]) -> Vec<u8> {
+    let mut section = Vec::with_capacity(1 + 66 * signatures.len());
+    section.push(signatures.len() as u8);
+    for sig in signatures {
+        section.extend_from_slice(sig);
+    }
+    section
+}
+
+/// Single keccak256 of a VAA body (the "message hash").
+///
+/// This is the hash the Solana Core Bridge stores in posted VAA accounts.
+pub fn vaa_body_hash(body: &[u8]) -> [u8; 32] {
+    Keccak256::digest(body).into()
+}
+
+/// Double keccak256 of a VAA body: the digest guardians sign.
+pub fn vaa_body_digest(body: &[u8]) -> [u8; 32] {
+    Keccak256::digest(vaa_body_hash(body)).into()
+}
+
+/// Both hashing stages of a VAA body, from [`digest_breakdown`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DigestBreakdown {
+    /// `keccak256(body)`.
+    pub single: [u8; 32],
+    /// `keccak256(keccak256(body))`, the signed digest.
+    pub double: [u8; 32],
+}
+
+/// Compute both hashing stages of a VAA body.
+///
+/// When signatures verify in one implementation but not another, comparing
+/// each stage against the other implementation shows whether the body
+/// serialization (`single` differs) or the second hash (`double` differs) is
+/// at fault.
+pub fn digest_breakdown(body: &[u8]) -> DigestBreakdown {
+    let single = vaa_body_hash(body);
+    DigestBreakdown {
+        single,
+        double: Keccak256::digest(single).into(),
+    }
+}
+
+/// How a chain's native emitter addresses are encoded in 32 bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EmitterFormat {
+    /// A 20-byte EVM address, left-padded with 12 zero bytes
+    /// (see [`emitter_address_from_20`]).
+    Evm20,
+    /// A native 32-byte address (Solana, Sui, Aptos).
+    Native32,
+}
+
+/// The emitter address format of a well-known chain, or `None` if the chain
+/// is not in the built-in table.
+pub fn emitter_format(chain: u16) -> Option<EmitterFormat> {
+    match chain {
+        // Solana, Sui, Aptos
+        1 | 21 | 22 => Some(EmitterFormat::Native32),
+        // Ethereum, BSC, Polygon, Avalanche, Fantom, Klaytn, Celo, Moonbeam,
+        // Arbitrum, Optimism, Base
+        2 | 4 | 5 | 6 | 10 | 13 | 14 | 16 | 23 | 24 | 30 => Some(EmitterFormat::Evm20),
+        _ => None,
+    }
+}
+
 /// Helper to create an emitter address from a 20-byte address (right-aligned).
 ///
 /// Useful for EVM-style addresses that are 20 bytes.
@@ -185,6 +960,33 @@ pub fn emitter_address_from_32(addr: [u8; 32]) -> [u8; 32] {
     addr
 }
 
+/// Parse an emitter address from hex, with or without a `0x` prefix.
+///
+/// Accepts 64 hex characters (a full 32-byte address) or 40 (a 20-byte EVM
+/// address, left-padded as in [`emitter_address_from_20`]).
+pub fn emitter_address_from_hex(hex_str: &str) -> Result<[u8; 32], hex::FromHexError> {
+    let hex_str = hex_str.strip_prefix("0x").unwrap_or(hex_str);
+    match hex_str.len() {
+        40 => {
+            let mut addr = [0u8; 20];
+            hex::decode_to_slice(hex_str, &mut addr)?;
+            Ok(emitter_address_from_20(addr))
+        }
+        64 => {
+            let mut addr = [0u8; 32];
+            hex::decode_to_slice(hex_str, &mut addr)?;
+            Ok(addr)
+        }
+        _ => Err(hex::FromHexError::InvalidStringLength),
+    }
+}
+
+/// Emitter address of a Solana program or account: its 32 pubkey bytes.
+#[cfg(feature = "litesvm")]
+pub fn emitter_address_from_pubkey(pubkey: &solana_sdk::pubkey::Pubkey) -> [u8; 32] {
+    pubkey.to_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -269,6 +1071,386 @@ mod tests {
         assert_eq!(signed[6 + 66], 3); // Second sig from guardian 3
     }
 
+    #[test]
+    fn test_with_sequence_edge() {
+        let base = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        for edge in SequenceEdge::ALL {
+            let vaa = base.clone().with_sequence_edge(edge);
+            assert_eq!(vaa.sequence, edge.value());
+            assert_eq!(&vaa.body()[42..50], &edge.value().to_be_bytes());
+        }
+        assert_eq!(SequenceEdge::U32Overflow.value() as u32, 0);
+        assert!((SequenceEdge::I64Overflow.value() as i64) < 0);
+    }
+
+    #[test]
+    fn test_signature_section_bytes() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        let section = vaa.signature_section_bytes(&guardians);
+        let signed = vaa.sign(&guardians);
+
+        assert_eq!(section.len(), 1 + 3 * 66);
+        assert_eq!(section[0], 3);
+        assert_eq!(&signed[5..5 + section.len()], &section[..]);
+        assert_eq!(&section[1..67], &vaa.guardian_signatures(&guardians)[0]);
+    }
+
+    #[test]
+    fn test_mine_digest_prefix() {
+        let base = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        let mined = base.mine_digest_prefix(&[0x42]).unwrap();
+        assert_eq!(mined.digest()[0], 0x42);
+        assert_eq!(mined.payload, base.payload);
+
+        assert_eq!(base.mine_digest_prefix(&[]).unwrap().nonce, base.nonce);
+    }
+
+    #[test]
+    fn test_serialized_len_matches_signed_vaa() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![0; 100]);
+
+        assert_eq!(vaa.serialized_len(5), vaa.sign(&guardians).len());
+        assert_eq!(vaa.serialized_len(0), 6 + vaa.body().len());
+    }
+
+    #[test]
+    fn test_sign_full() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        let signed = vaa.sign_full(&guardians);
+
+        assert_eq!(signed.vaa_bytes, vaa.sign(&guardians));
+        assert_eq!(
+            signed.guardian_signatures,
+            vaa.guardian_signatures(&guardians)
+        );
+    }
+
+    #[test]
+    fn test_new_validated() {
+        let evm = emitter_address_from_20([0xAB; 20]);
+
+        assert!(TestVaa::new_validated(2, evm, 0, vec![]).is_ok());
+        assert_eq!(
+            TestVaa::new_validated(2, [0xAB; 32], 0, vec![]).err(),
+            Some(TestVaaError::EmitterFormatMismatch {
+                chain: 2,
+                format: EmitterFormat::Evm20,
+                emitter_address: [0xAB; 32],
+            })
+        );
+
+        assert!(TestVaa::new_validated(1, [0xAB; 32], 0, vec![]).is_ok());
+        assert_eq!(
+            TestVaa::new_validated(1, evm, 0, vec![]).err(),
+            Some(TestVaaError::EmitterFormatMismatch {
+                chain: 1,
+                format: EmitterFormat::Native32,
+                emitter_address: evm,
+            })
+        );
+        assert!(TestVaa::new_validated(
+            crate::payloads::GOVERNANCE_CHAIN,
+            crate::payloads::GOVERNANCE_EMITTER,
+            0,
+            vec![]
+        )
+        .is_ok());
+
+        // Chains outside the table are not checked.
+        assert!(TestVaa::new_validated(9999, evm, 0, vec![]).is_ok());
+    }
+
+    #[test]
+    fn test_expect_payload() {
+        TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3])
+            .expect_payload(&[1, 2, 3])
+            .expect_payload(b"\x01\x02\x03");
+    }
+
+    #[test]
+    #[should_panic(expected = "payload differs at byte 2 (3 vs 4 bytes)")]
+    fn test_expect_payload_mismatch() {
+        TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]).expect_payload(&[1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn test_assemble_from_partials() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let body = vaa.body();
+
+        let assembled = vaa.assemble_from_partials(
+            5,
+            &[
+                guardians.sign_vaa_body_with(&body, &[3, 1]),
+                guardians.sign_vaa_body_with(&body, &[1, 0]),
+                guardians.sign_vaa_body_with(&body, &[4]),
+            ],
+        );
+
+        assert_eq!(assembled, vaa.sign_with(&guardians, &[0, 1, 3, 4]));
+        crate::verify_vaa_against_account(&assembled, &guardians.to_account_data(0)).unwrap();
+    }
+
+    #[test]
+    fn test_assemble_from_partials_unsorted() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+        let body = vaa.body();
+        let batches = [
+            guardians.sign_vaa_body_with(&body, &[2]),
+            guardians.sign_vaa_body_with(&body, &[0, 2, 1]),
+        ];
+
+        let unsorted = vaa.assemble_from_partials_unsorted(3, &batches);
+        assert_eq!(unsorted, vaa.sign_with(&guardians, &[2, 0, 1]));
+        assert!(matches!(
+            crate::verify_vaa_against_account(&unsorted, &guardians.to_account_data(0)),
+            Err(crate::VerifyError::NonIncreasingIndex { .. })
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "guardian index 4 is out of range for a set of 3")]
+    fn test_assemble_from_partials_rejects_out_of_range_index() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        vaa.assemble_from_partials(3, &[guardians.sign_vaa_body_with(&vaa.body(), &[4])]);
+    }
+
+    #[test]
+    fn test_contract_upgrade() {
+        use crate::payloads::{CORE_ACTION_CONTRACT_UPGRADE, CORE_MODULE, GOVERNANCE_EMITTER};
+
+        let vaa = TestVaa::contract_upgrade(CORE_MODULE, 1, [0x42; 32]);
+
+        assert_eq!(vaa.emitter_chain, 1);
+        assert_eq!(vaa.emitter_address, GOVERNANCE_EMITTER);
+        assert_eq!(vaa.payload[32], CORE_ACTION_CONTRACT_UPGRADE);
+        assert_eq!(&vaa.payload[35..], &[0x42; 32]);
+    }
+
+    #[test]
+    fn test_with_large_set_index_roundtrips() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]).with_large_set_index();
+        let signed = vaa.sign(&guardians);
+
+        assert_eq!(&signed[1..5], &[0xFE, 0xDC, 0xBA, 0x98]);
+        let (index, _, _) = crate::verify::parse_signed_vaa(&signed).unwrap();
+        assert_eq!(index, LARGE_GUARDIAN_SET_INDEX);
+        crate::verify_vaa_against_account(
+            &signed,
+            &guardians.to_account_data(LARGE_GUARDIAN_SET_INDEX),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_builder_fields_roundtrip_through_sign() {
+        let guardians = TestGuardianSet::generate(1, 1);
+        let vaa = TestVaa::builder()
+            .emitter_chain(2)
+            .emitter_address([0xCD; 32])
+            .sequence(7)
+            .payload(vec![1, 2, 3])
+            .timestamp(1_700_000_000)
+            .nonce(99)
+            .consistency_level(200)
+            .version(1)
+            .guardian_set_index(3)
+            .build();
+        let signed = vaa.sign(&guardians);
+
+        let (index, signatures, body) = crate::verify::parse_signed_vaa(&signed).unwrap();
+        assert_eq!(signed[0], 1);
+        assert_eq!(index, 3);
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(&body[0..4], &1_700_000_000u32.to_be_bytes());
+        assert_eq!(&body[4..8], &99u32.to_be_bytes());
+        assert_eq!(&body[8..10], &2u16.to_be_bytes());
+        assert_eq!(&body[10..42], &[0xCD; 32]);
+        assert_eq!(&body[42..50], &7u64.to_be_bytes());
+        assert_eq!(body[50], 200);
+        assert_eq!(&body[51..], &[1, 2, 3]);
+
+        assert_eq!(TestVaa::builder().version(2).build().sign(&guardians)[0], 2);
+    }
+
+    #[test]
+    fn test_builder_defaults_match_new() {
+        let built = TestVaa::builder()
+            .emitter_address([0xAB; 32])
+            .sequence(42)
+            .build();
+
+        assert_eq!(built.body(), TestVaa::new(1, [0xAB; 32], 42, vec![]).body());
+        assert_eq!(built.version, VAA_VERSION);
+        assert_eq!(built.guardian_set_index, 0);
+    }
+
+    #[test]
+    fn test_digest_is_what_guardians_sign() {
+        let guardian = TestGuardian::default();
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let signature = guardian.sign_vaa_body(&vaa.body());
+
+        assert_eq!(
+            crate::recover_eth_address(&vaa.digest(), &signature[1..].try_into().unwrap()),
+            Some(guardian.eth_address)
+        );
+    }
+
+    #[test]
+    fn test_minimal() {
+        let guardians = TestGuardianSet::generate(1, 1);
+        let vaa = TestVaa::minimal();
+
+        assert!(vaa.payload.is_empty());
+        assert_eq!(vaa.body().len(), VAA_BODY_HEADER_LEN);
+        assert_eq!(vaa.sign(&guardians).len(), vaa.serialized_len(1));
+        crate::verify_vaa_against_account(&vaa.sign(&guardians), &guardians.to_account_data(0))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_expired_and_future_timestamps() {
+        let now = 1_700_000_000;
+        let base = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        let expired = base.clone().expired(now, 3600);
+        assert_eq!(expired.timestamp, now - 3600);
+        assert_eq!(&expired.body()[0..4], &(now - 3600).to_be_bytes());
+        assert_eq!(base.clone().future(now, 60).timestamp, now + 60);
+
+        assert_eq!(base.clone().expired(10, 60).timestamp, 0);
+        assert_eq!(base.future(u32::MAX - 1, 60).timestamp, u32::MAX);
+    }
+
+    #[test]
+    fn test_with_version() {
+        let guardians = TestGuardianSet::generate(1, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let v2 = vaa.clone().with_version(2);
+
+        let signed = v2.sign(&guardians);
+        assert_eq!(signed[0], 2);
+        assert_eq!(&signed[1..], &vaa.sign(&guardians)[1..]);
+        assert!(matches!(
+            crate::verify_vaa_against_account(&signed, &guardians.to_account_data(0)),
+            Err(crate::VerifyError::MalformedVaa(_))
+        ));
+    }
+
+    #[test]
+    fn test_vaa_header_bytes() {
+        let guardians = TestGuardianSet::generate(2, 1);
+        let mut vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        vaa.guardian_set_index = 7;
+        let sigs = vaa.guardian_signatures(&guardians);
+
+        let header = vaa_header_bytes(1, 7, &sigs);
+        assert_eq!(header.len(), 6 + 66 * 2);
+        assert_eq!(header[0], 1);
+        assert_eq!(u32::from_be_bytes(header[1..5].try_into().unwrap()), 7);
+        assert_eq!(header[5], 2);
+        assert_eq!(&header[6..72], &sigs[0]);
+
+        let mut assembled = header;
+        assembled.extend_from_slice(&vaa.body());
+        assert_eq!(assembled, vaa.sign(&guardians));
+    }
+
+    #[test]
+    fn test_digest_breakdown() {
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let body = vaa.body();
+
+        let breakdown = digest_breakdown(&body);
+
+        assert_eq!(breakdown.single, vaa_body_hash(&body));
+        assert_eq!(breakdown.double, vaa.digest());
+        assert_eq!(
+            breakdown.double,
+            <[u8; 32]>::from(Keccak256::digest(breakdown.single))
+        );
+    }
+
+    #[test]
+    fn test_sign_quorum_boundary() {
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        let guardians = TestGuardianSet::generate(13, 1);
+        let at = vaa.sign_with_quorum(&guardians);
+        let below = vaa.sign_below_quorum(&guardians);
+        assert_eq!(at.len(), 9);
+        assert_eq!(below.len(), 8);
+
+        let addresses = guardians.eth_addresses();
+        assert!(crate::verify_signatures(&vaa.digest(), &at, &addresses).is_ok());
+        assert!(crate::verify_signatures(&vaa.digest(), &below, &addresses).is_err());
+
+        let single = TestGuardianSet::single(TestGuardian::default());
+        assert!(vaa.sign_below_quorum(&single).is_empty());
+    }
+
+    #[test]
+    fn test_with_repeated_payload() {
+        let vaa = TestVaa::with_repeated_payload(1, [0xAB; 32], 42, &[0xDE, 0xAD, 0xBE], 100);
+
+        assert_eq!(vaa.payload.len(), 300);
+        assert_eq!(&vaa.payload[297..], &[0xDE, 0xAD, 0xBE]);
+        assert_eq!(vaa.body().len(), VAA_BODY_HEADER_LEN + 300);
+    }
+
+    #[test]
+    fn test_across_chains() {
+        let vaas = TestVaa::across_chains(&[1, 2, 30], [0xAB; 32], 7, vec![9, 9]);
+
+        assert_eq!(vaas.len(), 3);
+        for (chain, vaa) in &vaas {
+            assert_eq!(vaa.emitter_chain, *chain);
+
+            let mut expected = vaas[0].1.body();
+            expected[8..10].copy_from_slice(&chain.to_be_bytes());
+            assert_eq!(vaa.body(), expected);
+        }
+    }
+
+    #[test]
+    fn test_sign_with_header_index() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3, 4]);
+
+        let signed = vaa.sign(&guardians);
+        let overridden = vaa.sign_with_header_index(&guardians, 7);
+
+        let offset = VAA_GUARDIAN_SET_INDEX_OFFSET;
+        let header_index = u32::from_be_bytes(overridden[offset..offset + 4].try_into().unwrap());
+        assert_eq!(header_index, 7);
+
+        // Everything after the header index is unchanged
+        assert_eq!(&overridden[offset + 4..], &signed[offset + 4..]);
+    }
+
+    #[test]
+    fn test_preflight() {
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3, 4]);
+
+        assert!(vaa.preflight(&TestGuardianSet::generate(13, 1)).is_ok());
+
+        let err = vaa.preflight(&TestGuardianSet::new(vec![])).unwrap_err();
+        assert!(err.contains("no quorum"), "{}", err);
+    }
+
     #[test]
     fn test_emitter_address_helpers() {
         let addr20 = [0xAB; 20];
@@ -277,4 +1459,32 @@ mod tests {
         assert_eq!(&result[0..12], &[0u8; 12]);
         assert_eq!(&result[12..32], &addr20);
     }
+
+    #[test]
+    fn test_emitter_address_from_hex() {
+        let padded =
+            emitter_address_from_hex("0xabababababababababababababababababababab").unwrap();
+        assert_eq!(padded, emitter_address_from_20([0xAB; 20]));
+
+        let full = emitter_address_from_hex(&"cd".repeat(32)).unwrap();
+        assert_eq!(full, [0xCD; 32]);
+        assert_eq!(
+            emitter_address_from_hex(&format!("0x{}", "cd".repeat(32))).unwrap(),
+            full
+        );
+
+        assert_eq!(
+            emitter_address_from_hex("0xabab"),
+            Err(hex::FromHexError::InvalidStringLength)
+        );
+        assert!(emitter_address_from_hex(&"zz".repeat(20)).is_err());
+    }
+
+    #[cfg(feature = "litesvm")]
+    #[test]
+    fn test_emitter_address_from_pubkey() {
+        let pubkey = solana_sdk::pubkey::Pubkey::new_from_array([0x11; 32]);
+
+        assert_eq!(emitter_address_from_pubkey(&pubkey), [0x11; 32]);
+    }
 }
@@ -0,0 +1,543 @@
+//! Offline guardian signature verification.
+//!
+//! Mirrors the checks the Verify VAA Shim performs on-chain (quorum, guardian
+//! index ordering and membership, signature recovery) so fixture mistakes can
+//! be caught in Rust without a round-trip through LiteSVM.
+
+use libsecp256k1::{Message, RecoveryId, Signature};
+use thiserror::Error;
+
+use crate::{eth_address_from_pubkey, quorum};
+
+/// Reasons a batch of guardian signatures would fail verification.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum VerifyError {
+    #[error(
+        "no quorum: {actual} signature(s) but {required} required for a set of {guardians} \
+         guardian(s)"
+    )]
+    NoQuorum {
+        actual: usize,
+        required: usize,
+        guardians: usize,
+    },
+    #[error(
+        "guardian indices must be strictly increasing: index {index} at position {position} \
+         follows index {previous} (sort signatures by guardian index and remove duplicates)"
+    )]
+    NonIncreasingIndex {
+        position: usize,
+        previous: u8,
+        index: u8,
+    },
+    #[error(
+        "guardian index {index} at position {position} is out of range for a set of \
+         {guardians} guardian(s)"
+    )]
+    IndexOutOfRange {
+        position: usize,
+        index: u8,
+        guardians: usize,
+    },
+    #[error("malformed VAA: {0}")]
+    MalformedVaa(String),
+    #[error("malformed guardian set account: {0}")]
+    MalformedGuardianSet(String),
+    #[error("VAA references guardian set {vaa} but the account holds set {account}")]
+    GuardianSetIndexMismatch { vaa: u32, account: u32 },
+    #[error("signature at position {position} is malformed (bad r/s or recovery id)")]
+    InvalidSignature { position: usize },
+    #[error(
+        "signature at position {position} recovers to 0x{} but guardian {index} is 0x{} \
+         (signed by a different guardian set, or over a different body?)",
+        hex::encode(.recovered),
+        hex::encode(.expected)
+    )]
+    KeyMismatch {
+        position: usize,
+        index: u8,
+        recovered: [u8; 20],
+        expected: [u8; 20],
+    },
+}
+
+/// Recover the Ethereum address that produced a 65-byte `[r, s, v]` signature.
+///
+/// Returns `None` if the signature or recovery id is malformed.
+pub fn recover_eth_address(digest: &[u8; 32], signature: &[u8; 65]) -> Option<[u8; 20]> {
+    let message = Message::parse(digest);
+    let sig = Signature::parse_standard_slice(&signature[..64]).ok()?;
+    let recovery_id = RecoveryId::parse(signature[64]).ok()?;
+    let public_key = libsecp256k1::recover(&message, &sig, &recovery_id).ok()?;
+
+    let raw: [u8; 64] = public_key.serialize()[1..].try_into().unwrap();
+    Some(eth_address_from_pubkey(&raw))
+}
+
+/// Assert that a 65-byte `[r, s, v]` signature over `digest` recovers to `expected`.
+///
+/// # Panics
+///
+/// Panics with both addresses if recovery yields a different address, or if
+/// the signature is malformed.
+pub fn assert_signature_recovers_to(digest: &[u8; 32], signature: &[u8; 65], expected: [u8; 20]) {
+    match recover_eth_address(digest, signature) {
+        Some(recovered) => assert!(
+            recovered == expected,
+            "signature recovers to 0x{}, expected 0x{}",
+            hex::encode(recovered),
+            hex::encode(expected)
+        ),
+        None => panic!(
+            "signature is malformed and recovers to no address, expected 0x{}",
+            hex::encode(expected)
+        ),
+    }
+}
+
+/// Verify 66-byte guardian signatures over `digest` against a guardian set.
+///
+/// Performs the same checks as the Verify VAA Shim, in the same order:
+/// 1. The number of signatures meets [`quorum`] for the set size
+/// 2. Guardian indices are strictly increasing
+/// 3. Every guardian index is a member of the set
+/// 4. Every signature recovers to the address at its guardian index
+pub fn verify_signatures(
+    digest: &[u8; 32],
+    signatures: &[[u8; 66]],
+    guardian_addresses: &[[u8; 20]],
+) -> Result<(), VerifyError> {
+    let required = quorum(guardian_addresses.len());
+    if signatures.len() < required {
+        return Err(VerifyError::NoQuorum {
+            actual: signatures.len(),
+            required,
+            guardians: guardian_addresses.len(),
+        });
+    }
+
+    let mut previous: Option<u8> = None;
+    for (position, sig) in signatures.iter().enumerate() {
+        let index = sig[0];
+
+        if let Some(previous) = previous {
+            if index <= previous {
+                return Err(VerifyError::NonIncreasingIndex {
+                    position,
+                    previous,
+                    index,
+                });
+            }
+        }
+        previous = Some(index);
+
+        let Some(&expected) = guardian_addresses.get(index as usize) else {
+            return Err(VerifyError::IndexOutOfRange {
+                position,
+                index,
+                guardians: guardian_addresses.len(),
+            });
+        };
+
+        let signature: [u8; 65] = sig[1..].try_into().unwrap();
+        let recovered = recover_eth_address(digest, &signature)
+            .ok_or(VerifyError::InvalidSignature { position })?;
+
+        if recovered != expected {
+            return Err(VerifyError::KeyMismatch {
+                position,
+                index,
+                recovered,
+                expected,
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Verify a serialized VAA against raw Core Bridge guardian set account data.
+///
+/// Parses the guardian set account (`index u32 | keys_len u32 | keys (20 each)
+/// | creation_time u32 | expiration_time u32`, little-endian), checks the VAA
+/// header references that index, then runs [`verify_signatures`] over the
+/// VAA body digest. Use it with account data read back from the SVM, e.g. to
+/// confirm that a rotated-in set actually verifies a VAA. The expiration time
+/// is not checked, as that depends on the clock.
+pub fn verify_vaa_against_account(
+    vaa_bytes: &[u8],
+    guardian_set_account_data: &[u8],
+) -> Result<(), VerifyError> {
+    let (account_index, guardian_addresses) =
+        parse_guardian_set_account(guardian_set_account_data)?;
+
+    let (vaa_index, signatures, body) = parse_signed_vaa(vaa_bytes)?;
+    if vaa_index != account_index {
+        return Err(VerifyError::GuardianSetIndexMismatch {
+            vaa: vaa_index,
+            account: account_index,
+        });
+    }
+
+    verify_signatures(
+        &crate::vaa_body_digest(body),
+        &signatures,
+        &guardian_addresses,
+    )
+}
+
+/// Assert that `vaa_bytes` verifies offline under exactly the expected sets.
+///
+/// Each entry pairs a guardian set with whether the VAA's signatures should
+/// verify against it (see [`verify_signatures`]). The header's guardian set
+/// index is not compared, so one VAA can be checked against every set in a
+/// rotation, e.g. `&[(&old, true), (&new, false)]`.
+///
+/// # Panics
+///
+/// Panics if `vaa_bytes` is malformed, or lists every set (by position in
+/// `expectations`) whose result was unexpected, with the verification error.
+pub fn assert_verifies_only_under(
+    vaa_bytes: &[u8],
+    expectations: &[(&crate::TestGuardianSet, bool)],
+) {
+    let (_, signatures, body) =
+        parse_signed_vaa(vaa_bytes).unwrap_or_else(|e| panic!("assert_verifies_only_under: {}", e));
+    let digest = crate::vaa_body_digest(body);
+
+    let unexpected: Vec<String> = expectations
+        .iter()
+        .enumerate()
+        .filter_map(|(position, (guardians, should_verify))| {
+            let result = verify_signatures(&digest, &signatures, &guardians.eth_addresses());
+            match (result, should_verify) {
+                (Ok(()), false) => Some(format!(
+                    "set {} verified but was expected to reject",
+                    position
+                )),
+                (Err(e), true) => Some(format!(
+                    "set {} rejected but was expected to verify: {}",
+                    position, e
+                )),
+                _ => None,
+            }
+        })
+        .collect();
+
+    assert!(unexpected.is_empty(), "{}", unexpected.join("\n"));
+}
+
+/// Assert that two serialized VAAs are byte-for-byte equal, reporting which
+/// fields differ.
+///
+/// Both sides are split into header fields (version, guardian set index,
+/// signature count), each signature, and body fields (timestamp, nonce,
+/// emitter chain, emitter address, sequence, consistency level, payload). The
+/// split is lenient: unsupported versions and truncated input still compare,
+/// with missing fields shown as empty.
+///
+/// # Panics
+///
+/// Panics if the bytes differ, listing every differing field with both values
+/// in hex.
+pub fn assert_vaa_bytes_eq(actual: &[u8], expected: &[u8]) {
+    if actual == expected {
+        return;
+    }
+
+    let field = |bytes: &[u8], start: usize, end: usize| -> Vec<u8> {
+        bytes[start.min(bytes.len())..end.min(bytes.len())].to_vec()
+    };
+    let num_signatures = |bytes: &[u8]| bytes.get(5).copied().unwrap_or(0) as usize;
+
+    let mut fields: Vec<(String, Vec<u8>, Vec<u8>)> = vec![
+        (
+            "version".to_string(),
+            field(actual, 0, 1),
+            field(expected, 0, 1),
+        ),
+        (
+            "guardian_set_index".to_string(),
+            field(actual, 1, 5),
+            field(expected, 1, 5),
+        ),
+        (
+            "num_signatures".to_string(),
+            field(actual, 5, 6),
+            field(expected, 5, 6),
+        ),
+    ];
+
+    let (actual_sigs, expected_sigs) = (num_signatures(actual), num_signatures(expected));
+    for i in 0..actual_sigs.max(expected_sigs) {
+        let start = 6 + 66 * i;
+        fields.push((
+            format!("signature[{}]", i),
+            if i < actual_sigs {
+                field(actual, start, start + 66)
+            } else {
+                Vec::new()
+            },
+            if i < expected_sigs {
+                field(expected, start, start + 66)
+            } else {
+                Vec::new()
+            },
+        ));
+    }
+
+    let (actual_body, expected_body) = (6 + 66 * actual_sigs, 6 + 66 * expected_sigs);
+    for (name, start, end) in [
+        ("timestamp", 0, 4),
+        ("nonce", 4, 8),
+        ("emitter_chain", 8, 10),
+        ("emitter_address", 10, 42),
+        ("sequence", 42, 50),
+        ("consistency_level", 50, 51),
+        ("payload", 51, usize::MAX),
+    ] {
+        fields.push((
+            name.to_string(),
+            field(actual, actual_body + start, actual_body.saturating_add(end)),
+            field(
+                expected,
+                expected_body + start,
+                expected_body.saturating_add(end),
+            ),
+        ));
+    }
+
+    let diffs: Vec<String> = fields
+        .iter()
+        .filter(|(_, a, e)| a != e)
+        .map(|(name, a, e)| format!("  {}: 0x{} != 0x{}", name, hex::encode(a), hex::encode(e)))
+        .collect();
+
+    panic!(
+        "VAAs differ ({} vs {} bytes), actual != expected:\n{}",
+        actual.len(),
+        expected.len(),
+        diffs.join("\n")
+    );
+}
+
+/// Split a serialized VAA into its guardian set index, signatures and body.
+pub(crate) fn parse_signed_vaa(
+    vaa_bytes: &[u8],
+) -> Result<(u32, Vec<[u8; 66]>, &[u8]), VerifyError> {
+    let malformed = |reason: &str| VerifyError::MalformedVaa(reason.to_string());
+    if vaa_bytes.len() < 6 {
+        return Err(malformed("shorter than the 6-byte header"));
+    }
+    if vaa_bytes[0] != 1 {
+        return Err(VerifyError::MalformedVaa(format!(
+            "unsupported version {}",
+            vaa_bytes[0]
+        )));
+    }
+
+    let guardian_set_index = u32::from_be_bytes(vaa_bytes[1..5].try_into().unwrap());
+
+    let num_signatures = vaa_bytes[5] as usize;
+    let body_start = 6 + 66 * num_signatures;
+    if vaa_bytes.len() < body_start {
+        return Err(malformed("truncated signatures"));
+    }
+
+    let signatures = vaa_bytes[6..body_start]
+        .chunks_exact(66)
+        .map(|sig| sig.try_into().unwrap())
+        .collect();
+
+    Ok((guardian_set_index, signatures, &vaa_bytes[body_start..]))
+}
+
+/// Parse the index and guardian addresses out of guardian set account data.
+pub(crate) fn parse_guardian_set_account(data: &[u8]) -> Result<(u32, Vec<[u8; 20]>), VerifyError> {
+    if data.len() < 8 {
+        return Err(VerifyError::MalformedGuardianSet(
+            "shorter than the 8-byte header".to_string(),
+        ));
+    }
+
+    let index = u32::from_le_bytes(data[0..4].try_into().unwrap());
+    let keys_len = u32::from_le_bytes(data[4..8].try_into().unwrap()) as usize;
+
+    let keys_end = 8 + 20 * keys_len;
+    if data.len() < keys_end {
+        return Err(VerifyError::MalformedGuardianSet(format!(
+            "{} bytes cannot hold {} keys",
+            data.len(),
+            keys_len
+        )));
+    }
+
+    let keys = data[8..keys_end]
+        .chunks_exact(20)
+        .map(|key| key.try_into().unwrap())
+        .collect();
+
+    Ok((index, keys))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{TestGuardian, TestGuardianSet, TestVaa};
+
+    #[test]
+    fn test_recover_eth_address_roundtrip() {
+        let guardian = TestGuardian::default();
+        let digest = [0x42; 32];
+
+        let signature = guardian.sign(&digest);
+
+        assert_eq!(
+            recover_eth_address(&digest, &signature),
+            Some(guardian.eth_address)
+        );
+    }
+
+    #[test]
+    fn test_assert_signature_recovers_to() {
+        let guardian = TestGuardian::default();
+        let digest = [0x42; 32];
+
+        assert_signature_recovers_to(&digest, &guardian.sign(&digest), guardian.eth_address);
+    }
+
+    #[test]
+    #[should_panic(expected = "signature recovers to 0x")]
+    fn test_assert_signature_recovers_to_mismatch() {
+        let guardian = TestGuardian::default();
+        let digest = [0x42; 32];
+
+        assert_signature_recovers_to(&digest, &guardian.sign(&digest), [0u8; 20]);
+    }
+
+    #[test]
+    fn test_verify_signatures_accepts_full_set() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        let sigs = vaa.guardian_signatures(&guardians);
+
+        assert_eq!(
+            verify_signatures(&vaa.digest(), &sigs, &guardians.eth_addresses()),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_below_quorum() {
+        let guardians = TestGuardianSet::generate(5, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        let sigs = guardians.sign_vaa_body_with(&vaa.body(), &[0, 1, 2]);
+
+        assert!(matches!(
+            verify_signatures(&vaa.digest(), &sigs, &guardians.eth_addresses()),
+            Err(VerifyError::NoQuorum { required: 4, .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_unsorted() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        let sigs = guardians.sign_vaa_body_with(&vaa.body(), &[0, 2, 1]);
+
+        assert!(matches!(
+            verify_signatures(&vaa.digest(), &sigs, &guardians.eth_addresses()),
+            Err(VerifyError::NonIncreasingIndex { position: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn test_verify_signatures_rejects_wrong_set() {
+        let signers = TestGuardianSet::generate(3, 1);
+        let other = TestGuardianSet::generate(3, 2);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![]);
+
+        let sigs = vaa.guardian_signatures(&signers);
+
+        assert!(matches!(
+            verify_signatures(&vaa.digest(), &sigs, &other.eth_addresses()),
+            Err(VerifyError::KeyMismatch { position: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_assert_verifies_only_under() {
+        let old = TestGuardianSet::generate(3, 1);
+        let new = TestGuardianSet::generate(3, 2);
+        let signed = TestVaa::new(1, [0xAB; 32], 42, vec![]).sign(&old);
+
+        assert_verifies_only_under(&signed, &[(&old, true), (&new, false)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "set 1 rejected but was expected to verify")]
+    fn test_assert_verifies_only_under_mismatch() {
+        let old = TestGuardianSet::generate(3, 1);
+        let new = TestGuardianSet::generate(3, 2);
+        let signed = TestVaa::new(1, [0xAB; 32], 42, vec![]).sign(&old);
+
+        assert_verifies_only_under(&signed, &[(&old, true), (&new, true)]);
+    }
+
+    #[test]
+    fn test_verify_vaa_against_account() {
+        let guardians = TestGuardianSet::generate(3, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let signed = vaa.sign(&guardians);
+
+        assert_eq!(
+            verify_vaa_against_account(&signed, &guardians.to_account_data(0)),
+            Ok(())
+        );
+        assert_eq!(
+            verify_vaa_against_account(&signed, &guardians.to_account_data(1)),
+            Err(VerifyError::GuardianSetIndexMismatch { vaa: 0, account: 1 })
+        );
+
+        let other = TestGuardianSet::generate(3, 2);
+        assert!(matches!(
+            verify_vaa_against_account(&signed, &other.to_account_data(0)),
+            Err(VerifyError::KeyMismatch { .. })
+        ));
+        assert!(matches!(
+            verify_vaa_against_account(&signed[..10], &guardians.to_account_data(0)),
+            Err(VerifyError::MalformedVaa(_))
+        ));
+    }
+
+    #[test]
+    fn test_assert_vaa_bytes_eq() {
+        let guardians = TestGuardianSet::generate(2, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        assert_vaa_bytes_eq(&vaa.sign(&guardians), &vaa.sign(&guardians));
+    }
+
+    #[test]
+    #[should_panic(expected = "sequence: 0x000000000000002b != 0x000000000000002a")]
+    fn test_assert_vaa_bytes_eq_reports_field() {
+        let guardians = TestGuardianSet::generate(2, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+        let mut other = vaa.clone();
+        other.sequence = 43;
+
+        assert_vaa_bytes_eq(&other.sign(&guardians), &vaa.sign(&guardians));
+    }
+
+    #[test]
+    #[should_panic(expected = "signature[1]: 0x != 0x01")]
+    fn test_assert_vaa_bytes_eq_reports_missing_signature() {
+        let guardians = TestGuardianSet::generate(2, 1);
+        let vaa = TestVaa::new(1, [0xAB; 32], 42, vec![1, 2, 3]);
+
+        assert_vaa_bytes_eq(&vaa.sign_with(&guardians, &[0]), &vaa.sign(&guardians));
+    }
+}
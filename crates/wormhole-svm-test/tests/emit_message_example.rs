@@ -18,13 +18,14 @@ use solana_sdk::{
     transaction::Transaction,
 };
 use wormhole_svm_definitions::{
-    find_core_bridge_config_address, find_emitter_sequence_address, find_event_authority_address,
-    find_fee_collector_address, find_shim_message_address,
+    find_core_bridge_config_address, find_emitter_sequence_address, find_fee_collector_address,
+    find_shim_message_address,
     solana::mainnet::{CORE_BRIDGE_PROGRAM_ID, POST_MESSAGE_SHIM_PROGRAM_ID},
 };
 use wormhole_svm_test::{
-    build_bridge_fee_ix, extract_posted_message_info_from_tx, read_emitter_sequence,
-    setup_wormhole, with_posted_signatures, TestGuardian, TestGuardianSet, WormholeProgramsConfig,
+    build_bridge_fee_ix, derive_post_message_event_authority, extract_posted_message_info_from_tx,
+    read_emitter_sequence, setup_wormhole, with_posted_signatures, TestGuardian, TestGuardianSet,
+    WormholeProgramsConfig,
 };
 
 // Message emitter example program ID (from the program's declare_id!)
@@ -52,7 +53,7 @@ fn build_emit_ix(payer: &Pubkey, nonce: u32, finality: u8, payload: &[u8]) -> In
     let (message, _) = find_shim_message_address(&emitter, &POST_MESSAGE_SHIM_PROGRAM_ID);
     let (sequence, _) = find_emitter_sequence_address(&emitter, &CORE_BRIDGE_PROGRAM_ID);
     let (fee_collector, _) = find_fee_collector_address(&CORE_BRIDGE_PROGRAM_ID);
-    let (event_authority, _) = find_event_authority_address(&POST_MESSAGE_SHIM_PROGRAM_ID);
+    let (event_authority, _) = derive_post_message_event_authority();
 
     let mut data = Vec::with_capacity(9 + payload.len());
     data.extend_from_slice(&nonce.to_le_bytes());
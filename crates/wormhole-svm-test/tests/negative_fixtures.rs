@@ -0,0 +1,222 @@
+//! Integration tests proving each negative fixture is rejected by the
+//! Verify VAA Shim when verified through the vaa-verifier-example program.
+
+#![cfg(feature = "bundled-fixtures")]
+
+use litesvm::{types::FailedTransactionMetadata, LiteSVM};
+use solana_sdk::{
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use wormhole_svm_definitions::solana::mainnet::VERIFY_VAA_SHIM_PROGRAM_ID;
+use wormhole_svm_test::{
+    emitter_address_from_20, negative_fixtures, negative_fixtures::NegativeFixture,
+    post_signatures, setup_wormhole, TestGuardianSet, TestVaa, WormholeProgramsConfig,
+};
+
+/// Set up Wormhole and the example program, install the fixture, and try to verify it.
+fn verify_fixture(
+    guardians: &TestGuardianSet,
+    fixture: &NegativeFixture,
+) -> Result<(), FailedTransactionMetadata> {
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    setup_wormhole(&mut svm, guardians, 0, WormholeProgramsConfig::default())
+        .expect("Failed to setup Wormhole");
+    svm.add_program_from_file(
+        vaa_verifier_example::ID,
+        "../../target/deploy/vaa_verifier_example.so",
+    )
+    .expect("Failed to load vaa_verifier_example program");
+
+    let wormhole = fixture.install(&mut svm);
+
+    let posted = post_signatures(
+        &mut svm,
+        &payer,
+        fixture.guardian_set_index,
+        &fixture.signatures,
+    )
+    .expect("post_signatures failed");
+
+    let verify_ix = vaa_verifier_example::build_verify_vaa_instruction(
+        &payer.pubkey(),
+        &wormhole.guardian_set,
+        &posted.pubkey,
+        wormhole.guardian_set_bump,
+        &fixture.vaa_body,
+    );
+    let tx = Transaction::new_signed_with_payer(
+        &[verify_ix],
+        Some(&payer.pubkey()),
+        &[&payer],
+        svm.latest_blockhash(),
+    );
+
+    svm.send_transaction(tx).map(|_| ())
+}
+
+/// Assert that the verify instruction failed inside the Verify VAA Shim's
+/// `verify_hash` CPI, and that the shim logged `expected_log`.
+fn assert_rejected_by_shim(failed: &FailedTransactionMetadata, expected_log: &str) {
+    assert!(
+        matches!(failed.err, TransactionError::InstructionError(0, _)),
+        "expected the verify instruction to fail with {:?}, got {:?}",
+        expected_log,
+        failed.err
+    );
+
+    let shim_failed = format!("Program {} failed", VERIFY_VAA_SHIM_PROGRAM_ID);
+    assert!(
+        failed
+            .meta
+            .logs
+            .iter()
+            .any(|log| log.starts_with(&shim_failed)),
+        "expected the shim to reject with {:?}\nlogs: {:#?}",
+        expected_log,
+        failed.meta.logs
+    );
+    assert!(
+        failed
+            .meta
+            .logs
+            .iter()
+            .any(|log| log.contains(expected_log)),
+        "expected the shim to log {:?}\nlogs: {:#?}",
+        expected_log,
+        failed.meta.logs
+    );
+}
+
+fn base_vaa() -> TestVaa {
+    TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"negative fixture".to_vec(),
+    )
+}
+
+#[test]
+fn test_expired_set_vaa_is_rejected() {
+    let guardians = TestGuardianSet::generate(3, 1);
+    let fixture = negative_fixtures::expired_set_vaa(&base_vaa(), &guardians);
+
+    let failed = verify_fixture(&guardians, &fixture).expect_err(fixture.expected_error);
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_wrong_index_vaa_is_rejected() {
+    let guardians = TestGuardianSet::generate(3, 1);
+    let fixture = negative_fixtures::wrong_index_vaa(&base_vaa(), &guardians);
+
+    let failed = verify_fixture(&guardians, &fixture).expect_err(fixture.expected_error);
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_insufficient_quorum_vaa_is_rejected() {
+    let guardians = TestGuardianSet::generate(3, 1);
+    let fixture = negative_fixtures::insufficient_quorum_vaa(&base_vaa(), &guardians);
+
+    let failed = verify_fixture(&guardians, &fixture).expect_err(fixture.expected_error);
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_tampered_vaa_is_rejected() {
+    let guardians = TestGuardianSet::generate(3, 1);
+    let fixture = negative_fixtures::tampered_vaa(&base_vaa(), &guardians);
+
+    let failed = verify_fixture(&guardians, &fixture).expect_err(fixture.expected_error);
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_unknown_set_vaa_is_rejected() {
+    let guardians = TestGuardianSet::generate(3, 1);
+    let fixture = negative_fixtures::unknown_set_vaa(&base_vaa(), &guardians);
+
+    let failed = verify_fixture(&guardians, &fixture).expect_err(fixture.expected_error);
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_assert_verify_rejects_tampered_vaa() {
+    use wormhole_svm_test::assert_verify_rejects;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::generate(3, 1);
+    let wormhole = setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default())
+        .expect("Failed to setup Wormhole");
+    svm.add_program_from_file(
+        vaa_verifier_example::ID,
+        "../../target/deploy/vaa_verifier_example.so",
+    )
+    .expect("Failed to load vaa_verifier_example program");
+
+    let fixture = negative_fixtures::tampered_vaa(&base_vaa(), &guardians);
+
+    let failed = assert_verify_rejects(
+        &mut svm,
+        &payer,
+        &fixture.vaa_bytes,
+        &fixture.signatures,
+        |sigs_pubkey, vaa_body| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs_pubkey,
+                wormhole.guardian_set_bump,
+                vaa_body,
+            )
+        },
+    );
+    assert_rejected_by_shim(&failed, fixture.expected_error);
+}
+
+#[test]
+fn test_non_canonical_guardian_set_bump_is_rejected() {
+    use wormhole_svm_test::assert_verify_rejects;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::generate(3, 1);
+    let wormhole = setup_wormhole(&mut svm, &guardians, 0, WormholeProgramsConfig::default())
+        .expect("Failed to setup Wormhole");
+    svm.add_program_from_file(
+        vaa_verifier_example::ID,
+        "../../target/deploy/vaa_verifier_example.so",
+    )
+    .expect("Failed to load vaa_verifier_example program");
+
+    let signed = base_vaa().sign_full(&guardians);
+
+    let failed = assert_verify_rejects(
+        &mut svm,
+        &payer,
+        &signed.vaa_bytes,
+        &signed.guardian_signatures,
+        |sigs_pubkey, vaa_body| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs_pubkey,
+                wormhole.non_canonical_bump(),
+                vaa_body,
+            )
+        },
+    );
+    // Set 0's canonical bump is 255, so the non-canonical bump wraps to 0,
+    // which does not derive a valid PDA.
+    assert_rejected_by_shim(&failed, negative_fixtures::SHIM_LOG_GUARDIAN_SET_ADDRESS);
+}
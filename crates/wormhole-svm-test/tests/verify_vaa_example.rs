@@ -629,3 +629,683 @@ fn test_emit_capture_verify_roundtrip() {
         "Message emitted on source chain → Captured by guardians → VAA verified on destination chain"
     );
 }
+
+/// Test verifying several VAAs in a single transaction.
+#[test]
+fn test_multi_verify_transaction() {
+    use wormhole_svm_test::build_multi_verify_transaction;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let vaas: Vec<TestVaa> = (0..3)
+        .map(|i| {
+            TestVaa::new(
+                1,
+                emitter_address_from_20([0xAB; 20]),
+                100 + i,
+                format!("batched VAA {}", i).into_bytes(),
+            )
+        })
+        .collect();
+    let bodies: Vec<Vec<u8>> = vaas.iter().map(|v| v.body()).collect();
+    let batches: Vec<Vec<[u8; 66]>> = vaas
+        .iter()
+        .map(|v| v.guardian_signatures(&guardians))
+        .collect();
+
+    let (tx, posted) = build_multi_verify_transaction(
+        &mut svm,
+        &payer,
+        GUARDIAN_SET_INDEX,
+        &batches,
+        |sigs_pubkey, i| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs_pubkey,
+                wormhole.guardian_set_bump,
+                &bodies[i],
+            )
+        },
+    )
+    .expect("build_multi_verify_transaction failed");
+
+    assert_eq!(posted.len(), 3);
+    assert_eq!(tx.message.instructions.len(), 3);
+
+    let result = svm.send_transaction(tx);
+    assert!(result.is_ok(), "Multi-verify failed: {:?}", result.err());
+
+    for p in &posted {
+        close_signatures(&mut svm, &payer, &p.pubkey, &payer.pubkey())
+            .expect("close_signatures failed");
+    }
+}
+
+#[test]
+fn test_max_payload_fits_single_transaction() {
+    use solana_sdk::packet::PACKET_DATA_SIZE;
+    use wormhole_svm_test::transaction_size;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let emitter = emitter_address_from_20([0xAB; 20]);
+    let build_tx = |svm: &LiteSVM, vaa: &TestVaa, signatures: &solana_sdk::pubkey::Pubkey| {
+        let ix = vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &wormhole.guardian_set,
+            signatures,
+            wormhole.guardian_set_bump,
+            &vaa.body(),
+        );
+        Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        )
+    };
+
+    // One byte too many no longer fits.
+    let oversized = TestVaa::with_oversized_payload(1, emitter, 1);
+    let tx = build_tx(&svm, &oversized, &solana_sdk::pubkey::Pubkey::new_unique());
+    assert_eq!(transaction_size(&tx), PACKET_DATA_SIZE + 1);
+
+    // The max payload fills the packet exactly and still verifies.
+    let vaa = TestVaa::with_max_payload(1, emitter, 2);
+    let posted = post_signatures(
+        &mut svm,
+        &payer,
+        GUARDIAN_SET_INDEX,
+        &vaa.guardian_signatures(&guardians),
+    )
+    .expect("post_signatures failed");
+
+    let tx = build_tx(&svm, &vaa, &posted.pubkey);
+    assert_eq!(transaction_size(&tx), PACKET_DATA_SIZE);
+
+    let result = svm.send_transaction(tx);
+    assert!(
+        result.is_ok(),
+        "Max-payload verification failed: {:?}",
+        result.err()
+    );
+
+    close_signatures(&mut svm, &payer, &posted.pubkey, &payer.pubkey())
+        .expect("close_signatures failed");
+}
+
+#[test]
+fn test_verify_vaa_twice() {
+    use wormhole_svm_test::verify_vaa_twice;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"replay".to_vec(),
+    );
+
+    let (first, second) = verify_vaa_twice(
+        &mut svm,
+        &payer,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        &vaa,
+        |svm, sigs_pubkey, vaa_body| {
+            let ix = vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs_pubkey,
+                wormhole.guardian_set_bump,
+                vaa_body,
+            );
+            let tx = Transaction::new_signed_with_payer(
+                &[ix],
+                Some(&payer.pubkey()),
+                &[&payer],
+                svm.latest_blockhash(),
+            );
+            svm.send_transaction(tx)
+        },
+    )
+    .expect("verify_vaa_twice failed");
+
+    // The example program only verifies and stores nothing, so it accepts replays.
+    assert!(first.is_ok(), "first attempt failed: {:?}", first.err());
+    assert!(second.is_ok(), "second attempt failed: {:?}", second.err());
+}
+
+#[test]
+fn test_expiring_guardian_set_lifecycle() {
+    use wormhole_svm_test::{expire_now, setup_wormhole_expiring};
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let set = setup_wormhole_expiring(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        3600,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"expiring".to_vec(),
+    );
+    let vaa_body = vaa.body();
+    let signatures = vaa.guardian_signatures(&guardians);
+
+    let verify = |svm: &mut LiteSVM| {
+        let posted = post_signatures(svm, &payer, GUARDIAN_SET_INDEX, &signatures)
+            .expect("post_signatures failed");
+        let ix = vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &set.accounts.guardian_set,
+            &posted.pubkey,
+            set.accounts.guardian_set_bump,
+            &vaa_body,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        close_signatures(svm, &payer, &posted.pubkey, &payer.pubkey())
+            .expect("close_signatures failed");
+        result
+    };
+
+    let before = verify(&mut svm);
+    assert!(
+        before.is_ok(),
+        "verification before expiry failed: {:?}",
+        before.err()
+    );
+
+    expire_now(&mut svm, &set);
+
+    assert!(
+        verify(&mut svm).is_err(),
+        "verification must fail once the guardian set has expired"
+    );
+}
+
+#[test]
+fn test_rotation_grace_period() {
+    use wormhole_svm_test::{expire_now, setup_wormhole_rotated, WormholeAccounts};
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let previous = TestGuardianSet::generate(3, 1);
+    let current = TestGuardianSet::generate(3, 2);
+    let sets = setup_wormhole_rotated(
+        &mut svm,
+        &previous,
+        0,
+        &current,
+        1,
+        3600,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"rotation".to_vec(),
+    );
+    let vaa_body = vaa.body();
+
+    let verify = |svm: &mut LiteSVM,
+                  guardians: &TestGuardianSet,
+                  index: u32,
+                  accounts: &WormholeAccounts| {
+        let signatures = vaa.guardian_signatures(guardians);
+        let posted =
+            post_signatures(svm, &payer, index, &signatures).expect("post_signatures failed");
+        let ix = vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &accounts.guardian_set,
+            &posted.pubkey,
+            accounts.guardian_set_bump,
+            &vaa_body,
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+        let result = svm.send_transaction(tx);
+        close_signatures(svm, &payer, &posted.pubkey, &payer.pubkey())
+            .expect("close_signatures failed");
+        result
+    };
+
+    let during_grace = verify(&mut svm, &previous, 0, &sets.previous.accounts);
+    assert!(
+        during_grace.is_ok(),
+        "previous set must verify during the grace period: {:?}",
+        during_grace.err()
+    );
+
+    expire_now(&mut svm, &sets.previous);
+
+    assert!(
+        verify(&mut svm, &previous, 0, &sets.previous.accounts).is_err(),
+        "previous set must be rejected after the grace period"
+    );
+
+    let current_result = verify(&mut svm, &current, sets.current_index, &sets.current);
+    assert!(
+        current_result.is_ok(),
+        "current set must verify after the grace period: {:?}",
+        current_result.err()
+    );
+}
+
+#[test]
+fn test_verify_cu_under_budget() {
+    use wormhole_svm_test::assert_verify_cu_under;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(1, emitter_address_from_20([0xAB; 20]), 42, b"cu".to_vec());
+    let signed = vaa.sign_full(&guardians);
+
+    let consumed = assert_verify_cu_under(
+        &mut svm,
+        &payer,
+        &signed.vaa_bytes,
+        &signed.guardian_signatures,
+        200_000,
+        |sigs, body| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs,
+                wormhole.guardian_set_bump,
+                body,
+            )
+        },
+    );
+    assert!(consumed > 0);
+}
+
+#[test]
+fn test_example_instruction_data_matches_program() {
+    use wormhole_svm_test::instruction_data::build_example_instruction_data;
+
+    let vaa = TestVaa::new(1, emitter_address_from_20([0xAB; 20]), 42, b"data".to_vec());
+
+    assert_eq!(
+        build_example_instruction_data(254, &vaa),
+        vaa_verifier_example::build_instruction_data(254, &vaa.body())
+    );
+}
+
+/// The Verify VAA Shim recovers signers through the `sol_secp256k1_recover`
+/// syscall, not the Secp256k1 precompile program, so verification must not
+/// depend on whether the precompile is loaded.
+#[test]
+fn test_verification_does_not_need_secp256k1_precompile() {
+    use wormhole_svm_test::FeatureSet;
+
+    let with_precompiles = LiteSVM::new();
+    let without_precompiles = LiteSVM::default()
+        .with_feature_set(FeatureSet::all_enabled())
+        .with_builtins()
+        .with_lamports(1_000_000_000_000_000)
+        .with_sysvars()
+        .with_default_programs()
+        .with_sigverify(true)
+        .with_blockhash_check(true);
+
+    for (label, mut svm) in [
+        ("with precompiles", with_precompiles),
+        ("without precompiles", without_precompiles),
+    ] {
+        let payer = Keypair::new();
+        svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+        let guardians = TestGuardianSet::single(TestGuardian::default());
+        let wormhole = setup_wormhole(
+            &mut svm,
+            &guardians,
+            GUARDIAN_SET_INDEX,
+            WormholeProgramsConfig::default(),
+        )
+        .expect("Failed to setup Wormhole");
+        load_example_program(&mut svm);
+
+        let vaa = TestVaa::new(
+            1,
+            emitter_address_from_20([0xAB; 20]),
+            42,
+            b"recover".to_vec(),
+        );
+        let posted = post_signatures(
+            &mut svm,
+            &payer,
+            GUARDIAN_SET_INDEX,
+            &vaa.guardian_signatures(&guardians),
+        )
+        .expect("post_signatures failed");
+
+        let verify_ix = vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &wormhole.guardian_set,
+            &posted.pubkey,
+            wormhole.guardian_set_bump,
+            &vaa.body(),
+        );
+        let tx = Transaction::new_signed_with_payer(
+            &[verify_ix],
+            Some(&payer.pubkey()),
+            &[&payer],
+            svm.latest_blockhash(),
+        );
+
+        let result = svm.send_transaction(tx);
+        assert!(
+            result.is_ok(),
+            "verification failed {}: {:?}",
+            label,
+            result.err()
+        );
+    }
+}
+
+#[test]
+fn test_verify_requires_posted_signatures() {
+    use solana_sdk::pubkey::Pubkey;
+    use wormhole_svm_test::{assert_requires_posted_signatures, create_rent_exempt_account};
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"unposted".to_vec(),
+    );
+    let garbage = create_rent_exempt_account(&mut svm, &payer, 100).unwrap();
+
+    for bogus in [Pubkey::new_unique(), garbage.pubkey()] {
+        assert_requires_posted_signatures(&mut svm, &payer, &vaa.body(), &bogus, |sigs, body| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &wormhole.guardian_set,
+                sigs,
+                wormhole.guardian_set_bump,
+                body,
+            )
+        });
+    }
+}
+
+#[test]
+fn test_verify_flow_trace() {
+    use wormhole_svm_test::verify_flow_trace;
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let guardians = TestGuardianSet::single(TestGuardian::default());
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"trace".to_vec(),
+    );
+    let trace = verify_flow_trace(&mut svm, &payer, &vaa, &guardians, |sigs, body| {
+        vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &wormhole.guardian_set,
+            sigs,
+            wormhole.guardian_set_bump,
+            body,
+        )
+    });
+
+    assert_eq!(trace.vaa_bytes, vaa.sign(&guardians));
+    assert_eq!(trace.digest, vaa.digest());
+    assert!(trace.post.succeeded(), "{:#?}", trace);
+
+    let verify = trace.verify.as_ref().unwrap();
+    assert!(verify.succeeded(), "{:#?}", trace);
+    assert!(verify.compute_units_consumed > 0);
+    assert!(!verify.logs.is_empty());
+
+    assert!(trace.close.as_ref().unwrap().succeeded(), "{:#?}", trace);
+    assert!(trace.reclaimed_lamports > 0);
+    wormhole_svm_test::assert_account_closed(&svm, &trace.signatures_account);
+}
+
+#[test]
+fn test_setup_wormhole_multi_rejects_other_and_expired_sets() {
+    use solana_sdk::clock::Clock;
+    use wormhole_svm_test::{assert_verify_rejects, setup_wormhole_multi, GuardianSetSpec};
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    let old = TestGuardianSet::generate(1, 1);
+    let new = TestGuardianSet::generate(1, 2);
+    let installed = setup_wormhole_multi(
+        &mut svm,
+        &[
+            GuardianSetSpec::new(0, &old).expiring_at(1_000),
+            GuardianSetSpec::new(1, &new),
+        ],
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+    load_example_program(&mut svm);
+
+    let mut clock = svm.get_sysvar::<Clock>();
+    clock.unix_timestamp = 1_001;
+    svm.set_sysvar(&clock);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"multi".to_vec(),
+    );
+    let verify_against = |index: u32| {
+        let (guardian_set, bump) = installed[&index];
+        move |sigs: &solana_sdk::pubkey::Pubkey, body: &[u8]| {
+            vaa_verifier_example::build_verify_vaa_instruction(
+                &payer.pubkey(),
+                &guardian_set,
+                sigs,
+                bump,
+                body,
+            )
+        }
+    };
+
+    // Signed by set 0, checked against set 1.
+    let signed_by_old = vaa.sign_full(&old);
+    assert_verify_rejects(
+        &mut svm,
+        &payer,
+        &signed_by_old.vaa_bytes,
+        &signed_by_old.guardian_signatures,
+        verify_against(1),
+    );
+
+    // Signed by set 0, checked against set 0 after it expired.
+    assert_verify_rejects(
+        &mut svm,
+        &payer,
+        &signed_by_old.vaa_bytes,
+        &signed_by_old.guardian_signatures,
+        verify_against(0),
+    );
+}
+
+#[test]
+fn test_gapped_guardian_set_verifies_by_index() {
+    use wormhole_svm_test::{
+        assert_verify_rejects, negative_fixtures::SHIM_LOG_KEY_MISMATCH, verify_flow_trace,
+    };
+
+    let mut svm = LiteSVM::new();
+    let payer = Keypair::new();
+    svm.airdrop(&payer.pubkey(), 100_000_000_000).unwrap();
+
+    // Guardians 0, 1 and 3: the installed set has four keys, with slot 2
+    // holding the gap placeholder.
+    let keys = TestGuardianSet::generate(3, 1);
+    let guardians = TestGuardianSet::from_indexed(vec![
+        (0, keys.get(0).unwrap().clone()),
+        (1, keys.get(1).unwrap().clone()),
+        (3, keys.get(2).unwrap().clone()),
+    ]);
+    let wormhole = setup_wormhole(
+        &mut svm,
+        &guardians,
+        GUARDIAN_SET_INDEX,
+        WormholeProgramsConfig::default(),
+    )
+    .expect("Failed to setup Wormhole");
+    load_example_program(&mut svm);
+
+    let vaa = TestVaa::new(
+        1,
+        emitter_address_from_20([0xAB; 20]),
+        42,
+        b"gapped".to_vec(),
+    );
+    let build_verify_ix = |sigs: &solana_sdk::pubkey::Pubkey, body: &[u8]| {
+        vaa_verifier_example::build_verify_vaa_instruction(
+            &payer.pubkey(),
+            &wormhole.guardian_set,
+            sigs,
+            wormhole.guardian_set_bump,
+            body,
+        )
+    };
+
+    // Signed by guardians 0, 1 and 3.
+    let trace = verify_flow_trace(&mut svm, &payer, &vaa, &guardians, build_verify_ix);
+    assert_eq!(
+        trace.vaa_bytes,
+        vaa.sign_with(&guardians, &[0, 1, 3]),
+        "{:#?}",
+        trace
+    );
+    assert!(trace.verify.as_ref().unwrap().succeeded(), "{:#?}", trace);
+
+    // Guardian 3's signature relabelled as the gap index 2.
+    let mut forged = guardians.sign_vaa_body_with(&vaa.body(), &[0, 1, 3]);
+    forged[2][0] = 2;
+    let failed =
+        assert_verify_rejects(&mut svm, &payer, &trace.vaa_bytes, &forged, build_verify_ix);
+    assert!(
+        failed
+            .meta
+            .logs
+            .iter()
+            .any(|log| log.contains(SHIM_LOG_KEY_MISMATCH)),
+        "{:#?}",
+        failed.meta.logs
+    );
+}